@@ -1,5 +1,8 @@
 //! Abstract Syntax Tree
 
+use crate::tok::{Tok, Tokenizer};
+use std::ops::BitOr;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Cmd {
     Explain(Stmt),
@@ -59,13 +62,14 @@ pub enum Stmt {
         if_not_exists: bool,
         tbl_name: QualifiedName,
         module_name: Name,
-        args: Option<Vec<Expr>>, // TODO Validate Expr
+        args: Option<ModuleArguments>,
     },
     Delete {
         with: Option<With>,
         tbl_name: QualifiedName,
         indexed: Option<Indexed>,
         where_clause: Option<Expr>,
+        returning: Option<Vec<ResultColumn>>,
         order_by: Option<Vec<SortedColumn>>,
         limit: Option<Limit>,
     },
@@ -93,6 +97,7 @@ pub enum Stmt {
         tbl_name: QualifiedName,
         columns: Option<Vec<Name>>,
         body: InsertBody,
+        returning: Option<Vec<ResultColumn>>,
     },
     // pragma name, body
     Pragma(QualifiedName, Option<PragmaBody>),
@@ -114,12 +119,14 @@ pub enum Stmt {
         tbl_name: QualifiedName,
         indexed: Option<Indexed>,
         sets: Vec<Set>,
+        from: Option<FromClause>,
         where_clause: Option<Expr>,
+        returning: Option<Vec<ResultColumn>>,
         order_by: Option<Vec<SortedColumn>>,
         limit: Option<Limit>,
     },
-    // database name
-    Vacuum(Option<Name>),
+    // database name, into expr
+    Vacuum(Option<Name>, Option<Expr>),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -144,6 +151,9 @@ pub enum Expr {
     },
     // COLLATE expression
     Collate(Box<Expr>, Name),
+    // Double-quoted identifier when DQS is enabled: like SQLite, name resolution must treat it
+    // as a string literal if it does not match a column
+    DoubleQuotedId(Name),
     // schema-name.table-name.column-name
     DoublyQualified(Name, Name, Name),
     // EXISTS subquery
@@ -153,9 +163,17 @@ pub enum Expr {
         name: String,
         distinctness: Option<Distinctness>,
         args: Option<Vec<Box<Expr>>>,
+        // ORDER BY inside the argument list of an aggregate
+        order_by: Option<Vec<SortedColumn>>,
+        filter_clause: Option<Box<Expr>>,
+        over_clause: Option<Box<Over>>,
     },
     // Function call expression with '*' as arg
-    FunctionCallStar(String),
+    FunctionCallStar {
+        name: String,
+        filter_clause: Option<Box<Expr>>,
+        over_clause: Option<Box<Over>>,
+    },
     // Identifier
     Id(Name),
     InList {
@@ -174,6 +192,12 @@ pub enum Expr {
         rhs: QualifiedName,
         args: Option<Vec<Box<Expr>>>,
     },
+    // boolean test: expr IS [NOT] TRUE/FALSE
+    IsBoolean {
+        lhs: Box<Expr>,
+        not: bool,
+        value: bool,
+    },
     Isnull(Box<Expr>),
     Like {
         lhs: Box<Expr>,
@@ -190,7 +214,7 @@ pub enum Expr {
     Parenthesized(Vec<Box<Expr>>),
     Qualified(Name, Name),
     // RAISE function call
-    Raise(ResolveType, Option<String>),
+    Raise(Raise),
     // Subquery expression
     Subquery(Box<Select>),
     // Unary expression
@@ -201,13 +225,26 @@ pub enum Expr {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Literal {
-    Numeric(String),
+    Numeric(String), // raw text, with digit separators
     String(String),
     Blob(String),
     Null,
     CurrentTime,
     CurrentDate,
     CurrentTimestamp,
+    // unquoted TRUE/FALSE, by default: like SQLite, name resolution must prefer a column with the same name
+    True,
+    False,
+}
+
+impl Literal {
+    // numeric value without digit separators
+    pub fn numeric_value(&self) -> Option<String> {
+        match self {
+            Literal::Numeric(n) => Some(n.replace('_', "")),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -222,6 +259,8 @@ pub enum LikeOperator {
 pub enum Operator {
     Add,
     And,
+    ArrowRight,      // JSON extraction (->)
+    ArrowRightShift, // JSON extraction (->>)
     BitwiseAnd,
     BitwiseOr,
     Concat, // String concatenation (||)
@@ -231,6 +270,8 @@ pub enum Operator {
     GreaterEquals,
     Is,
     IsNot,
+    IsDistinctFrom,
+    IsNotDistinctFrom,
     LeftShift,
     Less,
     LessEquals,
@@ -289,7 +330,8 @@ pub enum OneSelect {
         columns: Vec<ResultColumn>,
         from: Option<FromClause>,
         where_clause: Option<Expr>,
-        group_by: Option<GroupBy>,
+        group_by: Option<Box<GroupBy>>,
+        window_clause: Option<Vec<WindowDef>>,
     },
     Values(Vec<Vec<Expr>>),
 }
@@ -331,25 +373,54 @@ pub struct JoinedSelectTable {
 pub enum SelectTable {
     Table(QualifiedName, Option<As>, Option<Indexed>),
     TableCall(QualifiedName, Option<Vec<Expr>>, Option<As>),
-    Select(Select, Option<As>),
+    Select(Box<Select>, Option<As>),
     Sub(FromClause, Option<As>),
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum JoinOperator {
     Comma,
     TypedJoin {
-        natural: bool,
         join_type: Option<JoinType>,
+        // join keywords as written (LEFT and RIGHT imply OUTER in join_type)
+        keywords: Vec<JoinKeyword>,
     },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub enum JoinType {
-    Left,
-    LeftOuter,
-    Inner,
+pub enum JoinKeyword {
     Cross,
+    Full,
+    Inner,
+    Left,
+    Natural,
+    Outer,
+    Right,
+}
+
+// join type flags (like SQLite JT_*)
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct JoinType(u8);
+
+impl JoinType {
+    pub const INNER: JoinType = JoinType(0x01);
+    pub const CROSS: JoinType = JoinType(0x02);
+    pub const NATURAL: JoinType = JoinType(0x04);
+    pub const LEFT: JoinType = JoinType(0x08);
+    pub const RIGHT: JoinType = JoinType(0x10);
+    pub const OUTER: JoinType = JoinType(0x20);
+
+    pub fn contains(self, join_type: JoinType) -> bool {
+        self.0 & join_type.0 == join_type.0
+    }
+}
+
+impl BitOr for JoinType {
+    type Output = JoinType;
+
+    fn bitor(self, rhs: JoinType) -> JoinType {
+        JoinType(self.0 | rhs.0)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -378,6 +449,8 @@ pub enum AlterTableBody {
     // new table name
     RenameTo(Name),
     AddColumn(ColumnDefinition), // TODO distinction between ADD and ADD COLUMN
+    RenameColumn { old: Name, new: Name },
+    DropColumn(Name),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -385,9 +458,58 @@ pub enum CreateTableBody {
     ColumnsAndConstraints {
         columns: Vec<ColumnDefinition>,
         constraints: Option<Vec<NamedTableConstraint>>,
-        without: bool,
+        options: TableOptions,
     },
-    AsSelect(Select),
+    AsSelect(Box<Select>),
+}
+
+// set of table options
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableOptions(u8);
+
+impl TableOptions {
+    pub const NONE: TableOptions = TableOptions(0);
+    pub const WITHOUT_ROWID: TableOptions = TableOptions(1);
+    pub const STRICT: TableOptions = TableOptions(2);
+
+    pub fn contains(self, options: TableOptions) -> bool {
+        self.0 & options.0 == options.0
+    }
+}
+
+impl BitOr for TableOptions {
+    type Output = TableOptions;
+
+    fn bitor(self, rhs: TableOptions) -> TableOptions {
+        TableOptions(self.0 | rhs.0)
+    }
+}
+
+// virtual table module arguments: source text between parentheses
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModuleArguments(pub String);
+
+impl ModuleArguments {
+    // arguments separated by top-level commas, empty ones are ignored
+    pub fn split(&self) -> Vec<&str> {
+        let mut args = Vec::new();
+        let mut depth = 0;
+        let mut arg: Option<(usize, usize)> = None;
+        for (l, t, r) in Tokenizer::new(&self.0, 0).flatten() {
+            match t {
+                Tok::Comma if depth == 0 => {
+                    args.extend(arg.take().map(|(l, r)| &self.0[l..r]));
+                    continue;
+                }
+                Tok::LeftParen => depth += 1,
+                Tok::RightParen => depth -= 1,
+                _ => {}
+            }
+            arg = Some((arg.map_or(l, |(l, _)| l), r));
+        }
+        args.extend(arg.map(|(l, r)| &self.0[l..r]));
+        args
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -426,6 +548,16 @@ pub enum ColumnConstraint {
         clause: ForeignKeyClause,
         deref_clause: Option<DeferSubclause>,
     },
+    Generated {
+        expr: Expr,
+        typ: Option<GeneratedColumnType>,
+    },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GeneratedColumnType {
+    Stored,
+    Virtual, // default
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -459,6 +591,12 @@ pub enum SortOrder {
     Desc,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NullsOrder {
+    First,
+    Last,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DefaultValue {
     Expr(Expr), // TODO
@@ -518,18 +656,19 @@ pub enum Indexed {
 pub struct SortedColumn {
     pub expr: Expr,
     pub order: Option<SortOrder>,
+    pub nulls: Option<NullsOrder>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Limit {
-    pub expr: Expr,
-    pub offset: Option<Expr>, /* TODO distinction between LIMIT offset, count and LIMIT count
-                               * OFFSET offset */
+    pub expr: Box<Expr>,
+    pub offset: Option<Box<Expr>>, /* TODO distinction between LIMIT offset, count and LIMIT count
+                                    * OFFSET offset */
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum InsertBody {
-    Select(Select),
+    Select(Box<Select>, Option<Box<Upsert>>),
     DefaultValues,
 }
 
@@ -539,13 +678,45 @@ pub struct Set {
     pub expr: Expr,
 }
 
+// ON CONFLICT clause
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Upsert {
+    // conflict target, only optional on the last clause
+    pub index: Option<UpsertIndex>,
+    pub do_clause: UpsertDo,
+    pub next: Option<Box<Upsert>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpsertIndex {
+    pub targets: Vec<SortedColumn>,
+    pub where_clause: Option<Expr>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UpsertDo {
+    Set {
+        sets: Vec<Set>,
+        where_clause: Option<Expr>,
+    },
+    Nothing,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PragmaBody {
     Equals(PragmaValue),
     Call(PragmaValue),
 }
 
-pub type PragmaValue = String; // TODO
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PragmaValue {
+    Numeric(String), // signed number
+    String(String),
+    Name(Name), // including join keywords (like FULL)
+    On,
+    Delete,
+    Default,
+}
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum TriggerTime {
@@ -569,19 +740,20 @@ pub enum TriggerCmd {
         or_conflict: Option<ResolveType>,
         tbl_name: Name,
         sets: Vec<Set>,
+        from: Option<FromClause>,
         where_clause: Option<Expr>,
     },
     Insert {
         or_conflict: Option<ResolveType>,
         tbl_name: Name,
         col_names: Option<Vec<Name>>,
-        select: Select,
+        body: InsertBody,
     },
     Delete {
         tbl_name: Name,
         where_clause: Option<Expr>,
     },
-    Select(Select),
+    Select(Box<Select>),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -593,6 +765,15 @@ pub enum ResolveType {
     Replace,
 }
 
+// RAISE function call: IGNORE or an error message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Raise {
+    Ignore,
+    Rollback(Box<Expr>),
+    Abort(Box<Expr>),
+    Fail(Box<Expr>),
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct With {
     pub recursive: bool,
@@ -603,12 +784,21 @@ pub struct With {
 pub struct CommonTableExpr {
     pub tbl_name: Name,
     pub columns: Option<Vec<IndexedColumn>>,
+    pub materialized: Option<Materialized>,
     pub select: Select,
 }
 
+// materialization hint
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Materialized {
+    Yes,
+    No,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Type {
-    pub name: String, // TODO Validate
+    pub name: String, // source text of the words (with their original spacing)
+    pub words: Vec<Name>,
     pub size: Option<TypeSize>,
 }
 
@@ -618,6 +808,62 @@ pub enum TypeSize {
     TypeSize(String, String),
 }
 
+// OVER clause
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Over {
+    Window(Box<Window>),
+    // window name
+    Name(Name),
+}
+
+// Named window definition
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WindowDef {
+    pub name: Name,
+    pub window: Window,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Window {
+    // base window name
+    pub base: Option<Name>,
+    pub partition_by: Option<Vec<Expr>>,
+    pub order_by: Option<Vec<SortedColumn>>,
+    pub frame_clause: Option<FrameClause>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameClause {
+    pub mode: FrameMode,
+    pub start: FrameBound,
+    pub end: Option<FrameBound>,
+    pub exclude: Option<FrameExclude>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameMode {
+    Groups,
+    Range,
+    Rows,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FrameBound {
+    CurrentRow,
+    Following(Box<Expr>),
+    Preceding(Box<Expr>),
+    UnboundedFollowing,
+    UnboundedPreceding,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameExclude {
+    NoOthers,
+    CurrentRow,
+    Group,
+    Ties,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum TransactionType {
     Deferred, // default
@@ -1,7 +1,10 @@
 //! A SQL parser.
 //! Adapted from [SQLite parser](http://www.sqlite.org/src/artifact?ci=trunk&filename=src/parse.y)
 
-use crate::ast::Cmd;
+use crate::ast::{
+    Cmd, ColumnConstraint, DeferSubclause, Expr, FrameBound, JoinKeyword, JoinOperator, JoinType,
+    Literal, Name, NamedColumnConstraint, Type,
+};
 use crate::tok;
 use lalrpop_util;
 
@@ -12,10 +15,213 @@ mod test;
 
 pub type ParseError<'input> = lalrpop_util::ParseError<usize, tok::Tok<'input>, tok::Error>;
 
+// Syntax enabled or disabled like the equivalent SQLite compile-time options.
+// By default, everything is enabled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParserOptions {
+    // ORDER BY and LIMIT on DELETE and UPDATE (`SQLITE_ENABLE_UPDATE_DELETE_LIMIT`)
+    pub update_delete_limit: bool,
+    // CREATE/DROP TRIGGER (`SQLITE_OMIT_TRIGGER`)
+    pub triggers: bool,
+    // CREATE/DROP VIEW (`SQLITE_OMIT_VIEW`)
+    pub views: bool,
+    // CREATE VIRTUAL TABLE (`SQLITE_OMIT_VIRTUALTABLE`)
+    pub virtual_tables: bool,
+    // WITH clause (`SQLITE_OMIT_CTE`)
+    pub ctes: bool,
+    // WINDOW, OVER and FILTER clauses (`SQLITE_OMIT_WINDOWFUNC`)
+    pub windows: bool,
+    // double-quoted string literals (`SQLITE_DQS`): a double-quoted identifier in an expression
+    // is an `Expr::DoubleQuotedId` (instead of an `Expr::Id`) which may be resolved as a string
+    pub dqs: bool,
+}
+
+impl Default for ParserOptions {
+    fn default() -> ParserOptions {
+        ParserOptions {
+            update_delete_limit: true,
+            triggers: true,
+            views: true,
+            virtual_tables: true,
+            ctes: true,
+            windows: true,
+            dqs: true,
+        }
+    }
+}
+
 pub fn parse_sql<'input>(input: &'input str) -> Result<Vec<Option<Cmd>>, ParseError<'input>> {
+    parse_sql_with_options(input, &ParserOptions::default())
+}
+
+pub fn parse_sql_with_options<'input>(
+    input: &'input str,
+    options: &ParserOptions,
+) -> Result<Vec<Option<Cmd>>, ParseError<'input>> {
     use self::lrsql::CmdListParser;
     let tokenizer = tok::Tokenizer::new(input, 0);
-    let sql = CmdListParser::new().parse(input, tokenizer)?;
+    let sql = CmdListParser::new().parse(input, *options, tokenizer)?;
 
     Ok(sql)
 }
+
+// Rejects the syntax of a feature disabled by `ParserOptions`.
+fn check_enabled<'input>(
+    enabled: bool,
+    code: tok::ErrorCode,
+    l: usize,
+    text: &'input str,
+) -> Result<(), ParseError<'input>> {
+    if enabled {
+        Ok(())
+    } else {
+        user_error(code, l, text)
+    }
+}
+
+// Like `tok::error`, but as a user error of the parser.
+fn user_error<'input, T>(
+    code: tok::ErrorCode,
+    l: usize,
+    text: &'input str,
+) -> Result<T, ParseError<'input>> {
+    tok::error(code, l, text).map_err(|error| ParseError::User { error })
+}
+
+// sqlite3ExprIdToTrueFalse: unquoted TRUE/FALSE identifiers are boolean literals.
+// With DQS, double-quoted identifiers are kept apart for name resolution.
+fn id_expr(id: Name, raw: &str, dqs: bool) -> Expr {
+    if raw.eq_ignore_ascii_case("true") {
+        Expr::Literal(Literal::True)
+    } else if raw.eq_ignore_ascii_case("false") {
+        Expr::Literal(Literal::False)
+    } else if dqs && raw.starts_with('"') {
+        Expr::DoubleQuotedId(id)
+    } else {
+        Expr::Id(id)
+    }
+}
+
+// An unquoted UNBOUNDED is a keyword before PRECEDING (at the start of a frame)
+// or FOLLOWING (at the end of a frame) and an identifier otherwise.
+fn frame_bound<'input>(
+    expr: Expr,
+    raw: &str,
+    bound: tok::Spanned<tok::Tok<'input>>,
+    unbounded: bool,
+) -> Result<FrameBound, ParseError<'input>> {
+    let preceding = bound.1 == tok::Tok::Preceding;
+    if !raw.eq_ignore_ascii_case("unbounded") {
+        Ok(if preceding {
+            FrameBound::Preceding(Box::new(expr))
+        } else {
+            FrameBound::Following(Box::new(expr))
+        })
+    } else if !unbounded {
+        let expected = if preceding { "following" } else { "preceding" };
+        Err(ParseError::UnrecognizedToken {
+            token: bound,
+            expected: vec![format!("\"{expected}\"")],
+        })
+    } else if preceding {
+        Ok(FrameBound::UnboundedPreceding)
+    } else {
+        Ok(FrameBound::UnboundedFollowing)
+    }
+}
+
+// sqlite3AddColumn: a trailing GENERATED ALWAYS is dropped from a column type
+// (`x INT GENERATED ALWAYS AS (1)` is parsed as the type `INT GENERATED ALWAYS` then `AS (1)`).
+fn column_type(mut col_type: Type) -> Option<Type> {
+    if let (None, [.., generated, always]) = (&col_type.size, &col_type.words[..])
+        && generated.eq_ignore_ascii_case("generated")
+        && always.eq_ignore_ascii_case("always")
+        && let Some(name) = strip_suffix_ignore_case(&col_type.name, "always")
+            .and_then(|name| strip_suffix_ignore_case(name.trim_end(), "generated"))
+    {
+        col_type.name = name.trim_end().to_owned();
+        col_type.words.truncate(col_type.words.len() - 2);
+        if col_type.words.is_empty() {
+            return None;
+        }
+    }
+    Some(col_type)
+}
+
+fn strip_suffix_ignore_case<'s>(s: &'s str, suffix: &str) -> Option<&'s str> {
+    let i = s.len().checked_sub(suffix.len())?;
+    s.get(i..)?.eq_ignore_ascii_case(suffix).then(|| &s[..i])
+}
+
+// sqlite3DeferForeignKey: applies a defer subclause to the last foreign key,
+// or returns it when there is none.
+fn defer_foreign_key<'c>(
+    constraints: impl DoubleEndedIterator<Item = &'c mut NamedColumnConstraint>,
+    clause: DeferSubclause,
+) -> Option<DeferSubclause> {
+    match constraints
+        .rev()
+        .map(|c| &mut c.constraint)
+        .find(|c| matches!(c, ColumnConstraint::ForeignKey { .. }))
+    {
+        Some(ColumnConstraint::ForeignKey { deref_clause, .. }) => {
+            *deref_clause = Some(clause);
+            None
+        }
+        _ => Some(clause),
+    }
+}
+
+// sqlite3JoinType
+fn join_operator<'input>(
+    spans: &[(usize, usize)],
+    text: &'input str,
+) -> Result<JoinOperator, ParseError<'input>> {
+    let mut jt = JoinType::default();
+    let mut keywords = Vec::with_capacity(spans.len());
+    let mut error = false;
+    for &(l, r) in spans {
+        let kw = &text[l..r];
+        let (t, keyword) = if kw.eq_ignore_ascii_case("natural") {
+            (JoinType::NATURAL, JoinKeyword::Natural)
+        } else if kw.eq_ignore_ascii_case("left") {
+            (JoinType::LEFT | JoinType::OUTER, JoinKeyword::Left)
+        } else if kw.eq_ignore_ascii_case("outer") {
+            (JoinType::OUTER, JoinKeyword::Outer)
+        } else if kw.eq_ignore_ascii_case("right") {
+            (JoinType::RIGHT | JoinType::OUTER, JoinKeyword::Right)
+        } else if kw.eq_ignore_ascii_case("full") {
+            (
+                JoinType::LEFT | JoinType::RIGHT | JoinType::OUTER,
+                JoinKeyword::Full,
+            )
+        } else if kw.eq_ignore_ascii_case("inner") {
+            (JoinType::INNER, JoinKeyword::Inner)
+        } else if kw.eq_ignore_ascii_case("cross") {
+            (JoinType::INNER | JoinType::CROSS, JoinKeyword::Cross)
+        } else {
+            error = true;
+            continue;
+        };
+        // LEFT RIGHT, LEFT FULL, ...
+        if (t.contains(JoinType::LEFT) || t.contains(JoinType::RIGHT))
+            && (jt.contains(JoinType::LEFT) || jt.contains(JoinType::RIGHT))
+        {
+            error = true;
+        }
+        jt = jt | t;
+        keywords.push(keyword);
+    }
+    if error
+        || jt.contains(JoinType::INNER | JoinType::OUTER)
+        || (jt.contains(JoinType::OUTER)
+            && !jt.contains(JoinType::LEFT)
+            && !jt.contains(JoinType::RIGHT))
+    {
+        return user_error(tok::ErrorCode::UnknownJoinType, spans[0].0, text);
+    }
+    Ok(JoinOperator::TypedJoin {
+        join_type: Some(jt),
+        keywords,
+    })
+}
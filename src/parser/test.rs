@@ -1,4 +1,19 @@
-use super::parse_sql;
+use super::{ParseError, ParserOptions, parse_sql, parse_sql_with_options};
+use crate::ast::{
+    Cmd, ColumnConstraint, CreateTableBody, DeferSubclause, Expr, InitDeferredPred, JoinKeyword,
+    JoinOperator, JoinType, Literal, OneSelect, Operator, PragmaBody, PragmaValue, QualifiedName,
+    Raise, ResultColumn, SelectTable, Stmt, Type, TypeSize,
+};
+use crate::tok::{ErrorCode, Tok};
+
+fn single_stmt(sql: &str) -> Stmt {
+    let mut cmds = parse_sql(sql).unwrap();
+    assert_eq!(1, cmds.len(), "single statement expected");
+    match cmds.remove(0) {
+        Some(Cmd::Stmt(stmt)) => stmt,
+        cmd => panic!("statement expected: {cmd:?}"),
+    }
+}
 
 #[test]
 fn test_begin() {
@@ -98,6 +113,11 @@ fn test_create_table() {
     parse_sql("CREATE TEMP TABLE test (col)").unwrap();
     parse_sql("CREATE TABLE IF NOT EXISTS test (col)").unwrap();
 
+    parse_sql("CREATE TABLE test (id PRIMARY KEY) WITHOUT ROWID").unwrap();
+    parse_sql("CREATE TABLE test (id INT) STRICT").unwrap();
+    parse_sql("CREATE TABLE test (id INT PRIMARY KEY) STRICT, WITHOUT ROWID").unwrap();
+    parse_sql("CREATE TABLE without (without)").unwrap();
+
     assert!(
         parse_sql("CREATE TABLE test").is_err(),
         "error expected when no column list is specified"
@@ -114,6 +134,26 @@ fn test_create_table() {
         parse_sql("CREATE TABLE test (col,)").is_err(),
         "error expected with trailing comma"
     );
+    assert!(
+        parse_sql("CREATE TABLE test (id) WITHOUT OID").is_err(),
+        "error expected with unknown WITHOUT option"
+    );
+    assert!(
+        parse_sql("CREATE TABLE test (id) LOOSE").is_err(),
+        "error expected with unknown table option"
+    );
+    assert!(
+        parse_sql("CREATE TABLE test (id) 'strict'").is_err(),
+        "error expected with quoted STRICT option"
+    );
+    assert!(
+        parse_sql("CREATE TABLE test (id) WITHOUT \"rowid\"").is_err(),
+        "error expected with quoted ROWID option"
+    );
+    assert!(
+        parse_sql("CREATE TABLE test (id) STRICT,").is_err(),
+        "error expected with trailing comma"
+    );
 }
 
 #[test]
@@ -124,6 +164,45 @@ fn test_column_definition() {
     parse_sql("CREATE TABLE test (id VARYING CHARACTER(255))").unwrap();
     parse_sql("CREATE TABLE test (id DOUBLE PRECISION)").unwrap();
     parse_sql("CREATE TABLE test (id DECIMAL(10,5))").unwrap();
+    parse_sql("CREATE TABLE test (id 'TEXT' COLLATE 'nocase')").unwrap();
+    parse_sql("SELECT CAST(x AS UNSIGNED BIG INT), CAST(x AS 'REAL') FROM test").unwrap();
+
+    let typ = column_type("CREATE TABLE test (id UNSIGNED  BIG INT)");
+    assert_eq!("UNSIGNED  BIG INT", typ.name);
+    assert_eq!(vec!["UNSIGNED", "BIG", "INT"], typ.words);
+    assert_eq!(None, typ.size);
+    let typ = column_type("CREATE TABLE test (id DOUBLE PRECISION(10, 2) NOT NULL)");
+    assert_eq!("DOUBLE PRECISION", typ.name);
+    assert_eq!(vec!["DOUBLE", "PRECISION"], typ.words);
+    assert_eq!(
+        Some(TypeSize::TypeSize("10".to_owned(), "2".to_owned())),
+        typ.size
+    );
+    let typ = column_type("CREATE TABLE test (id 'VARYING' CHARACTER(-255))");
+    assert_eq!("'VARYING' CHARACTER", typ.name);
+    assert_eq!(vec!["VARYING", "CHARACTER"], typ.words);
+    assert_eq!(Some(TypeSize::MaxSize("-255".to_owned())), typ.size);
+    let typ = column_type("CREATE TABLE test (id INT generated)");
+    assert_eq!("INT generated", typ.name);
+    assert_eq!(vec!["INT", "generated"], typ.words);
+    let typ = column_type("CREATE TABLE test (total INT  Generated Always AS (1), a)");
+    assert_eq!("INT", typ.name);
+    assert_eq!(vec!["INT"], typ.words);
+    let typ = column_type("CREATE TABLE test (total GENERATED AS (1), a)");
+    assert_eq!("GENERATED", typ.name);
+    assert_eq!(vec!["generated"], typ.words);
+    parse_sql("SELECT CAST(x AS generated) FROM test").unwrap();
+}
+
+fn column_type(sql: &str) -> Type {
+    let Stmt::CreateTable {
+        body: CreateTableBody::ColumnsAndConstraints { mut columns, .. },
+        ..
+    } = single_stmt(sql)
+    else {
+        panic!("CREATE TABLE expected");
+    };
+    columns.remove(0).col_type.expect("column type expected")
 }
 
 #[test]
@@ -137,6 +216,249 @@ fn test_column_constraints() {
     parse_sql("CREATE TABLE test (id COLLATE NOCASE)").unwrap();
     parse_sql("CREATE TABLE test (id REFERENCES fktable(id))").unwrap();
     parse_sql("CREATE TABLE test (id REFERENCES fktable(id) ON DELETE CASCADE)").unwrap();
+    parse_sql("CREATE TABLE test (a, b, total INT GENERATED ALWAYS AS (a + b) STORED)").unwrap();
+    parse_sql("CREATE TABLE test (a, b, total INT GENERATED ALWAYS AS (a + b) VIRTUAL)").unwrap();
+    parse_sql("CREATE TABLE test (a, total AS (a * 2) NOT NULL)").unwrap();
+    parse_sql("CREATE TABLE test (a, total AS (a * 2) stored UNIQUE)").unwrap();
+
+    assert!(
+        parse_sql("CREATE TABLE test (a, total AS (a * 2) unknown)").is_err(),
+        "error expected with unknown generated column type"
+    );
+    parse_sql("CREATE TABLE test (a, total NOT NULL GENERATED ALWAYS AS (a * 2))").unwrap();
+    parse_sql("CREATE TABLE test (a, total CONSTRAINT g GENERATED ALWAYS AS (a * 2))").unwrap();
+    assert!(
+        parse_sql("CREATE TABLE test (a, total NOT NULL GENERATED AS (a * 2))").is_err(),
+        "error expected when ALWAYS is missing"
+    );
+    assert_eq!(
+        ErrorCode::UnsupportedNullsOrder,
+        error_code("CREATE TABLE test (a INT PRIMARY KEY DESC NULLS FIRST)")
+    );
+}
+
+#[test]
+fn test_column_defer_subclause() {
+    assert_eq!(
+        Some(DeferSubclause {
+            deferrable: false,
+            init_deferred: None
+        }),
+        column_defer_subclause("CREATE TABLE test (id REFERENCES fktable(id) NOT DEFERRABLE)")
+    );
+    assert_eq!(
+        Some(DeferSubclause {
+            deferrable: true,
+            init_deferred: Some(InitDeferredPred::InitiallyDeferred)
+        }),
+        column_defer_subclause(
+            "CREATE TABLE test (id REFERENCES fktable(id) ON DELETE CASCADE DEFERRABLE INITIALLY \
+             DEFERRED)"
+        )
+    );
+    assert_eq!(
+        Some(DeferSubclause {
+            deferrable: false,
+            init_deferred: Some(InitDeferredPred::InitiallyImmediate)
+        }),
+        column_defer_subclause(
+            "CREATE TABLE test (id REFERENCES fktable(id) NOT NULL NOT DEFERRABLE INITIALLY \
+             IMMEDIATE)"
+        )
+    );
+    assert_eq!(
+        Some(DeferSubclause {
+            deferrable: false,
+            init_deferred: None
+        }),
+        column_defer_subclause(
+            "CREATE TABLE test (id CONSTRAINT fk REFERENCES fktable(id) CONSTRAINT d NOT \
+             DEFERRABLE)"
+        )
+    );
+    assert_eq!(
+        None,
+        column_defer_subclause("CREATE TABLE test (id REFERENCES fktable(id) NOT NULL)")
+    );
+    assert_eq!(
+        Some(DeferSubclause {
+            deferrable: false,
+            init_deferred: None
+        }),
+        column_defer_subclause(
+            "CREATE TABLE test (id REFERENCES fktable(id), x NOT NULL NOT DEFERRABLE, y)"
+        )
+    );
+    assert_eq!(
+        Some(DeferSubclause {
+            deferrable: true,
+            init_deferred: None
+        }),
+        column_defer_subclause(
+            "CREATE TABLE test (id REFERENCES fktable(id), x NOT DEFERRABLE, y DEFERRABLE \
+             REFERENCES fktable(x))"
+        )
+    );
+    parse_sql("CREATE TABLE test (id NOT NULL NOT DEFERRABLE)")
+        .expect("defer subclause without foreign key ignored");
+}
+
+fn column_defer_subclause(sql: &str) -> Option<DeferSubclause> {
+    let Stmt::CreateTable {
+        body: CreateTableBody::ColumnsAndConstraints { mut columns, .. },
+        ..
+    } = single_stmt(sql)
+    else {
+        panic!("CREATE TABLE expected");
+    };
+    columns
+        .remove(0)
+        .constraints
+        .into_iter()
+        .find_map(|c| match c.constraint {
+            ColumnConstraint::ForeignKey { deref_clause, .. } => Some(deref_clause),
+            _ => None,
+        })
+        .expect("foreign key expected")
+}
+
+#[test]
+fn test_fallback_id() {
+    parse_sql("CREATE TABLE generated (generated INT, unbounded)").unwrap();
+    parse_sql("SELECT generated, unbounded FROM generated").unwrap();
+    parse_sql("SELECT sum(unbounded) OVER (ORDER BY unbounded) FROM test").unwrap();
+    parse_sql("CREATE TABLE test (match TEXT, database TEXT, left INT)").unwrap();
+    parse_sql("SELECT match, database, left, current_date FROM test").unwrap();
+    parse_sql("SELECT like('a%', x), glob('a*', x), replace(x, 'a', 'b'), left(x) FROM test")
+        .unwrap();
+    parse_sql("SELECT with, end FROM test WHERE match = 1").unwrap();
+    parse_sql("INSERT INTO test (match, column, if) VALUES (1, 2, 3)").unwrap();
+    parse_sql("SELECT x AS like FROM test AS if").unwrap();
+    parse_sql("CREATE TABLE test (x DEFAULT cast, y DEFAULT current_date)").unwrap();
+}
+
+#[test]
+fn test_keywords_as_identifiers() {
+    // Like SQLite: %fallback ID, JOIN_KW (nm), INDEXED (id),
+    // and FILTER, OVER, WINDOW which are only keywords in their context.
+    fn is_name(tok: &Tok) -> bool {
+        tok.is_fallback_id()
+            || matches!(
+                tok,
+                Tok::Cross
+                    | Tok::Full
+                    | Tok::Inner
+                    | Tok::Left
+                    | Tok::Natural
+                    | Tok::Outer
+                    | Tok::Right
+                    | Tok::Indexed
+                    | Tok::Filter
+                    | Tok::Over
+                    | Tok::Window
+            )
+    }
+    // Like SQLite, CAST, RAISE and CURRENT_DATE/TIME/TIMESTAMP are expected in an expression.
+    fn is_expr_name(tok: &Tok) -> bool {
+        is_name(tok)
+            && !matches!(
+                tok,
+                Tok::Cast
+                    | Tok::Raise
+                    | Tok::CurrentDate
+                    | Tok::CurrentTime
+                    | Tok::CurrentTimestamp
+            )
+    }
+    // Like SQLite, JOIN_KW and INDEXED are not type names (ids).
+    fn is_type_name(tok: &Tok) -> bool {
+        is_name(tok)
+            && !matches!(
+                tok,
+                Tok::Cross
+                    | Tok::Full
+                    | Tok::Inner
+                    | Tok::Left
+                    | Tok::Natural
+                    | Tok::Outer
+                    | Tok::Right
+                    | Tok::Indexed
+            )
+    }
+    fn select_core(sql: &str) -> Option<OneSelect> {
+        let cmds = parse_sql(sql).ok()?;
+        let [Some(Cmd::Stmt(Stmt::Select(select)))] = <[_; 1]>::try_from(cmds).ok()? else {
+            return None;
+        };
+        Some(select.body.select)
+    }
+    fn type_words(sql: &str) -> Option<Vec<String>> {
+        let cmds = parse_sql(sql).ok()?;
+        let [
+            Some(Cmd::Stmt(Stmt::CreateTable {
+                body: CreateTableBody::ColumnsAndConstraints { mut columns, .. },
+                ..
+            })),
+        ] = <[_; 1]>::try_from(cmds).ok()?
+        else {
+            return None;
+        };
+        Some(columns.remove(0).col_type?.words)
+    }
+    for (kw, tok) in crate::tok::KEYWORDS {
+        let sql = format!("CREATE TABLE test ({kw} INT)");
+        assert_eq!(
+            is_name(tok),
+            parse_sql(&sql).is_ok(),
+            "{kw} accepted as column name"
+        );
+
+        let operand = select_core(&format!("SELECT {kw} FROM test"));
+        assert_eq!(
+            is_expr_name(tok),
+            matches!(operand, Some(OneSelect::Select { columns, .. })
+                if matches!(columns[..], [ResultColumn::Expr(Expr::Id(_), None)])),
+            "{kw} accepted as expression operand"
+        );
+
+        let call = select_core(&format!("SELECT {kw}(1)"));
+        assert_eq!(
+            is_expr_name(tok),
+            matches!(call, Some(OneSelect::Select { columns, .. })
+                if matches!(columns[..], [ResultColumn::Expr(Expr::FunctionCall { .. }, None)])),
+            "{kw} accepted as function name"
+        );
+
+        let table = select_core(&format!("SELECT * FROM main.{kw}"));
+        assert_eq!(
+            is_name(tok),
+            matches!(table, Some(OneSelect::Select { from: Some(from), .. })
+                if matches!(*from.select, SelectTable::Table(QualifiedName { db_name: Some(_), .. }, None, None))),
+            "{kw} accepted as qualified table name"
+        );
+
+        let sql = format!("WITH {kw} AS (SELECT 1) SELECT 1");
+        assert_eq!(
+            is_name(tok),
+            parse_sql(&sql).is_ok(),
+            "{kw} accepted as common table name"
+        );
+
+        let cast = select_core(&format!("SELECT CAST(x AS {kw})"));
+        assert_eq!(
+            is_type_name(tok),
+            matches!(cast, Some(OneSelect::Select { columns, .. })
+                if matches!(columns[..], [ResultColumn::Expr(Expr::Cast { .. }, None)])),
+            "{kw} accepted as CAST type name"
+        );
+
+        let words = type_words(&format!("CREATE TABLE test (x INT {kw})"));
+        assert_eq!(
+            is_type_name(tok),
+            words.is_some_and(|words| words.len() == 2),
+            "{kw} accepted as type word"
+        );
+    }
 }
 
 #[test]
@@ -154,6 +476,15 @@ fn test_table_constraints() {
          DEFERRED)",
     )
     .expect("FK constraint with defer clause supported");
+
+    assert!(
+        parse_sql("CREATE TABLE test (id, PRIMARY KEY (id NULLS LAST))").is_err(),
+        "error expected with NULLS LAST in PK constraint"
+    );
+    assert!(
+        parse_sql("CREATE TABLE test (id, UNIQUE (id DESC NULLS FIRST))").is_err(),
+        "error expected with NULLS FIRST in UNIQUE constraint"
+    );
 }
 
 #[test]
@@ -200,11 +531,35 @@ fn test_drop_view() {
 fn test_select() {
     parse_sql("SELECT 1").unwrap();
     parse_sql("SELECT 1, 'test'").unwrap();
+    parse_sql("SELECT 1 'one' FROM test 't'").unwrap();
+    parse_sql("SELECT RAISE(IGNORE)").unwrap();
+
+    assert_eq!(
+        vec![ResultColumn::Expr(
+            Expr::Raise(Raise::Fail(Box::new(Expr::Binary(
+                Box::new(Expr::Literal(Literal::String("x = ".to_owned()))),
+                Operator::Concat,
+                Box::new(Expr::Id("x".to_owned()))
+            )))),
+            None
+        )],
+        result_columns("SELECT RAISE(FAIL, 'x = ' || x)")
+    );
+    assert!(
+        parse_sql("SELECT RAISE(IGNORE, 'msg')").is_err(),
+        "error expected with IGNORE and a message"
+    );
+    assert!(
+        parse_sql("SELECT RAISE(ABORT)").is_err(),
+        "error expected when no message is specified"
+    );
 
     parse_sql("SELECT * FROM test WHERE 1").unwrap();
     parse_sql("SELECT * FROM test WHERE 1 GROUP BY id HAVING count(*) > 1").unwrap();
     parse_sql("SELECT * FROM test ORDER BY 1").unwrap();
     parse_sql("SELECT * FROM test ORDER BY 1, id").unwrap();
+    parse_sql("SELECT * FROM test ORDER BY id NULLS FIRST, name DESC NULLS LAST").unwrap();
+    parse_sql("SELECT first, last, nulls FROM test ORDER BY nulls NULLS LAST").unwrap();
     parse_sql("SELECT * FROM test LIMIT 1").unwrap();
 
     assert!(
@@ -213,11 +568,245 @@ fn test_select() {
     );
 }
 
+#[test]
+fn test_join() {
+    parse_sql("SELECT * FROM a, b").unwrap();
+    parse_sql("SELECT * FROM a JOIN b ON a.id = b.id").unwrap();
+    parse_sql("SELECT * FROM a NATURAL JOIN b").unwrap();
+    parse_sql("SELECT * FROM a LEFT JOIN b USING (id)").unwrap();
+    parse_sql("SELECT * FROM a LEFT OUTER JOIN b USING (id)").unwrap();
+    parse_sql("SELECT * FROM a RIGHT JOIN b USING (id)").unwrap();
+    parse_sql("SELECT * FROM a RIGHT OUTER JOIN b USING (id)").unwrap();
+    parse_sql("SELECT * FROM a FULL JOIN b USING (id)").unwrap();
+    parse_sql("SELECT * FROM a FULL OUTER JOIN b USING (id)").unwrap();
+    parse_sql("SELECT * FROM a NATURAL FULL OUTER JOIN b").unwrap();
+    parse_sql("SELECT * FROM a INNER JOIN b ON a.id = b.id").unwrap();
+    parse_sql("SELECT * FROM a NATURAL INNER JOIN b").unwrap();
+    parse_sql("SELECT * FROM a CROSS JOIN b").unwrap();
+    parse_sql("SELECT * FROM a natural left outer join b").unwrap();
+
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: None,
+            keywords: vec![]
+        },
+        join_operator("SELECT * FROM a JOIN b")
+    );
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: Some(JoinType::LEFT | JoinType::OUTER),
+            keywords: vec![JoinKeyword::Left]
+        },
+        join_operator("SELECT * FROM a LEFT JOIN b")
+    );
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: Some(JoinType::LEFT | JoinType::OUTER),
+            keywords: vec![JoinKeyword::Left, JoinKeyword::Outer]
+        },
+        join_operator("SELECT * FROM a LEFT OUTER JOIN b")
+    );
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: Some(JoinType::LEFT | JoinType::RIGHT | JoinType::OUTER),
+            keywords: vec![JoinKeyword::Full]
+        },
+        join_operator("SELECT * FROM a FULL JOIN b")
+    );
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: Some(JoinType::LEFT | JoinType::RIGHT | JoinType::OUTER),
+            keywords: vec![JoinKeyword::Full, JoinKeyword::Outer]
+        },
+        join_operator("SELECT * FROM a FULL OUTER JOIN b")
+    );
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: Some(JoinType::NATURAL | JoinType::LEFT | JoinType::OUTER),
+            keywords: vec![JoinKeyword::Natural, JoinKeyword::Left]
+        },
+        join_operator("SELECT * FROM a NATURAL LEFT JOIN b")
+    );
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: Some(JoinType::NATURAL | JoinType::LEFT | JoinType::OUTER),
+            keywords: vec![JoinKeyword::Left, JoinKeyword::Natural]
+        },
+        join_operator("SELECT * FROM a LEFT NATURAL JOIN b")
+    );
+    assert_eq!(
+        JoinOperator::TypedJoin {
+            join_type: Some(JoinType::INNER | JoinType::CROSS),
+            keywords: vec![JoinKeyword::Cross]
+        },
+        join_operator("SELECT * FROM a cross JOIN b")
+    );
+    assert_eq!(JoinOperator::Comma, join_operator("SELECT * FROM a, b"));
+
+    assert!(
+        parse_sql("SELECT * FROM a LEFT RIGHT JOIN b").is_err(),
+        "error expected with LEFT RIGHT JOIN"
+    );
+    assert!(
+        parse_sql("SELECT * FROM a OUTER JOIN b").is_err(),
+        "error expected with OUTER JOIN"
+    );
+    assert!(
+        parse_sql("SELECT * FROM a INNER OUTER JOIN b").is_err(),
+        "error expected with INNER OUTER JOIN"
+    );
+    assert!(
+        parse_sql("SELECT * FROM a CROSS LEFT JOIN b").is_err(),
+        "error expected with CROSS LEFT JOIN"
+    );
+    assert!(
+        parse_sql("SELECT * FROM a LEFT foo JOIN b").is_err(),
+        "error expected with unknown join keyword"
+    );
+    assert!(
+        parse_sql("SELECT * FROM a NATURAL LEFT OUTER OUTER JOIN b").is_err(),
+        "error expected with more than three join keywords"
+    );
+}
+
+fn join_operator(sql: &str) -> JoinOperator {
+    let Stmt::Select(select) = single_stmt(sql) else {
+        panic!("SELECT expected");
+    };
+    let OneSelect::Select {
+        from: Some(from), ..
+    } = select.body.select
+    else {
+        panic!("SELECT with FROM clause expected");
+    };
+    let mut joins = from.joins.expect("join expected");
+    joins.remove(0).operator
+}
+
+#[test]
+fn test_with() {
+    parse_sql("WITH cte AS (SELECT 1) SELECT * FROM cte").unwrap();
+    parse_sql(
+        "WITH RECURSIVE cte (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM cte) SELECT n FROM cte",
+    )
+    .unwrap();
+    parse_sql("WITH cte AS MATERIALIZED (SELECT 1) SELECT * FROM cte").unwrap();
+    parse_sql("WITH a AS NOT MATERIALIZED (SELECT 1), b AS (SELECT 2) SELECT * FROM a, b").unwrap();
+    parse_sql("WITH materialized AS (SELECT 1) SELECT materialized.* FROM materialized").unwrap();
+    parse_sql(
+        "WITH like AS (SELECT 1) SELECT * FROM (WITH glob(a) AS (SELECT 1) SELECT * FROM glob)",
+    )
+    .unwrap();
+    parse_sql("SELECT 1 IN (WITH cte AS (SELECT 1), match AS (SELECT 1) SELECT * FROM match)")
+        .unwrap();
+
+    assert!(
+        parse_sql("WITH cte AS NOT (SELECT 1) SELECT * FROM cte").is_err(),
+        "error expected when MATERIALIZED is missing after NOT"
+    );
+}
+
 #[test]
 fn test_expression() {
     // TODO
 }
 
+#[test]
+fn test_is_distinct_from() {
+    parse_sql("SELECT * FROM test WHERE a IS DISTINCT FROM b").unwrap();
+    parse_sql("SELECT * FROM test WHERE a IS NOT DISTINCT FROM b + 1 AND a IS NOT b").unwrap();
+    parse_sql("SELECT a IS DISTINCT FROM NULL, a IS NULL FROM test").unwrap();
+
+    assert!(
+        parse_sql("SELECT * FROM test WHERE a IS DISTINCT b").is_err(),
+        "error expected when FROM is missing"
+    );
+    assert!(
+        parse_sql("SELECT * FROM test WHERE a DISTINCT FROM b").is_err(),
+        "error expected when IS is missing"
+    );
+}
+
+fn result_columns(sql: &str) -> Vec<ResultColumn> {
+    let Stmt::Select(select) = single_stmt(sql) else {
+        panic!("SELECT expected");
+    };
+    let OneSelect::Select { columns, .. } = select.body.select else {
+        panic!("SELECT core expected");
+    };
+    columns
+}
+
+#[test]
+fn test_numeric_literal() {
+    let ResultColumn::Expr(Expr::Literal(literal), _) = &result_columns("SELECT 1_000_000")[0]
+    else {
+        panic!("literal expected");
+    };
+    assert_eq!(Literal::Numeric("1_000_000".to_owned()), *literal);
+    assert_eq!(Some("1000000".to_owned()), literal.numeric_value());
+
+    assert!(
+        parse_sql("SELECT 1_000_").is_err(),
+        "error expected with trailing digit separator"
+    );
+}
+
+#[test]
+fn test_boolean() {
+    assert_eq!(
+        vec![
+            ResultColumn::Expr(Expr::Literal(Literal::True), None),
+            ResultColumn::Expr(Expr::Literal(Literal::False), None),
+            ResultColumn::Expr(Expr::DoubleQuotedId("true".to_owned()), None),
+            ResultColumn::Expr(Expr::Id("false".to_owned()), None),
+        ],
+        result_columns("SELECT true, FALSE, \"true\", [false]")
+    );
+    assert_eq!(
+        vec![
+            ResultColumn::Expr(
+                Expr::IsBoolean {
+                    lhs: Box::new(Expr::Id("x".to_owned())),
+                    not: false,
+                    value: true,
+                },
+                None
+            ),
+            ResultColumn::Expr(
+                Expr::IsBoolean {
+                    lhs: Box::new(Expr::Id("x".to_owned())),
+                    not: true,
+                    value: false,
+                },
+                None
+            ),
+            ResultColumn::Expr(
+                Expr::Binary(
+                    Box::new(Expr::Id("x".to_owned())),
+                    Operator::Is,
+                    Box::new(Expr::DoubleQuotedId("true".to_owned()))
+                ),
+                None
+            ),
+        ],
+        result_columns("SELECT x IS TRUE, x IS NOT FALSE, x IS \"true\"")
+    );
+    parse_sql("CREATE TABLE test (flag DEFAULT true)").unwrap();
+}
+
+#[test]
+fn test_json_operators() {
+    parse_sql("SELECT data->'$.name' FROM test").unwrap();
+    parse_sql("SELECT data->>'$.name' FROM test WHERE data->>'$.id' = 1").unwrap();
+    parse_sql("SELECT data->'a'->>'b' || 'c' COLLATE nocase FROM test").unwrap();
+
+    assert!(
+        parse_sql("SELECT data-> FROM test").is_err(),
+        "error expected when no path is specified"
+    );
+}
+
 #[test]
 fn test_delete() {
     parse_sql("DELETE FROM test").unwrap();
@@ -226,6 +815,9 @@ fn test_delete() {
     parse_sql("DELETE FROM test WHERE 1").unwrap();
     parse_sql("DELETE FROM test ORDER BY id").unwrap();
     parse_sql("DELETE FROM test LIMIT 1").unwrap();
+    parse_sql("DELETE FROM test RETURNING *").unwrap();
+    parse_sql("DELETE FROM test WHERE 1 RETURNING id, name AS n ORDER BY id LIMIT 1").unwrap();
+    parse_sql("WITH cte AS (SELECT 1) DELETE FROM test RETURNING id + 1 AS next").unwrap();
 
     assert!(
         parse_sql("DELETE FROM").is_err(),
@@ -242,7 +834,25 @@ fn test_update() {
     parse_sql("UPDATE test SET id = 1 WHERE 1").unwrap();
     parse_sql("UPDATE test SET id = 1 ORDER BY id").unwrap();
     parse_sql("UPDATE test SET id = 1 LIMIT 1").unwrap();
+    parse_sql("UPDATE test SET id = 1 RETURNING *").unwrap();
+    parse_sql("UPDATE test SET id = 1 WHERE 1 RETURNING id AS i, test.* ORDER BY id LIMIT 1")
+        .unwrap();
+
+    parse_sql("UPDATE test SET id = other.id FROM other WHERE test.name = other.name").unwrap();
+    parse_sql(
+        "UPDATE test SET id = s.id FROM staging AS s JOIN other o ON s.id = o.id, (SELECT 1) \
+         WHERE test.name = s.name RETURNING *",
+    )
+    .unwrap();
 
+    assert!(
+        parse_sql("UPDATE test SET id = 1 FROM WHERE 1").is_err(),
+        "error expected when no table name is specified in FROM clause"
+    );
+    assert!(
+        parse_sql("UPDATE test SET id = 1 ORDER BY id RETURNING *").is_err(),
+        "error expected when RETURNING follows ORDER BY"
+    );
     assert!(
         parse_sql("UPDATE SET id = 1").is_err(),
         "error expected when no table name is specified"
@@ -268,6 +878,16 @@ fn test_insert() {
     parse_sql("REPLACE INTO test VALUES (1)").unwrap();
     parse_sql("INSERT OR IGNORE INTO test VALUES (1)").unwrap();
 
+    parse_sql("INSERT INTO test VALUES (1) RETURNING *").unwrap();
+    parse_sql("INSERT INTO test SELECT * FROM other RETURNING id AS i").unwrap();
+    parse_sql("INSERT INTO test DEFAULT VALUES RETURNING id").unwrap();
+    parse_sql("INSERT INTO test VALUES (1) ON CONFLICT DO NOTHING RETURNING *").unwrap();
+    parse_sql(
+        "INSERT INTO test VALUES (1) ON CONFLICT (id) DO UPDATE SET id = 2 WHERE 1 RETURNING \
+         rowid",
+    )
+    .unwrap();
+
     parse_sql("UPDATE test SET id = 1 WHERE 1").unwrap();
     parse_sql("UPDATE test SET id = 1 ORDER BY id").unwrap();
     parse_sql("UPDATE test SET id = 1 LIMIT 1").unwrap();
@@ -278,6 +898,140 @@ fn test_insert() {
     );
 }
 
+#[test]
+fn test_upsert() {
+    parse_sql("INSERT INTO test VALUES (1) ON CONFLICT DO NOTHING").unwrap();
+    parse_sql("INSERT INTO test VALUES (1) ON CONFLICT (id) DO NOTHING").unwrap();
+    parse_sql("INSERT INTO test VALUES (1) ON CONFLICT (id) WHERE id > 0 DO NOTHING").unwrap();
+    parse_sql("INSERT INTO test VALUES (1) ON CONFLICT (id) DO UPDATE SET name = excluded.name")
+        .unwrap();
+    parse_sql(
+        "INSERT INTO test VALUES (1) ON CONFLICT (id COLLATE nocase DESC) DO UPDATE SET (id, \
+         name) = (1, 'test') WHERE id > 0",
+    )
+    .unwrap();
+    parse_sql(
+        "INSERT INTO test VALUES (1) ON CONFLICT (id) DO NOTHING ON CONFLICT (name) DO UPDATE \
+         SET id = 1 ON CONFLICT DO NOTHING",
+    )
+    .expect("several ON CONFLICT clauses supported");
+    parse_sql("INSERT INTO test SELECT * FROM other WHERE true ON CONFLICT DO NOTHING").unwrap();
+    parse_sql(
+        "INSERT INTO test SELECT * FROM a JOIN b ON a.id = b.id WHERE 1 ON CONFLICT DO NOTHING",
+    )
+    .unwrap();
+    parse_sql("INSERT INTO test SELECT * FROM other GROUP BY id ON CONFLICT DO NOTHING").unwrap();
+    parse_sql("INSERT INTO test SELECT * FROM other ORDER BY id ON CONFLICT DO NOTHING").unwrap();
+    parse_sql("INSERT INTO test SELECT * FROM other LIMIT 1 ON CONFLICT DO NOTHING").unwrap();
+    parse_sql("INSERT INTO test SELECT 1 UNION SELECT * FROM other WHERE 1 ON CONFLICT DO NOTHING")
+        .unwrap();
+
+    assert!(
+        parse_sql("INSERT INTO test SELECT * FROM other ON CONFLICT DO NOTHING").is_err(),
+        "error expected when the SELECT ends with a FROM clause"
+    );
+    assert!(
+        parse_sql("INSERT INTO test SELECT * FROM a JOIN b ON CONFLICT DO NOTHING").is_err(),
+        "error expected when the SELECT ends with a join"
+    );
+    assert!(
+        parse_sql("INSERT INTO test VALUES (1) ON CONFLICT DO NOTHING ON CONFLICT DO NOTHING")
+            .is_err(),
+        "error expected when a conflict target is missing before the last ON CONFLICT clause"
+    );
+    assert!(
+        parse_sql("INSERT INTO test DEFAULT VALUES ON CONFLICT DO NOTHING").is_err(),
+        "error expected with DEFAULT VALUES"
+    );
+}
+
+#[test]
+fn test_aggregate() {
+    parse_sql("SELECT count(*) FILTER (WHERE id > 0) FROM test").unwrap();
+    parse_sql("SELECT count(DISTINCT id) FILTER (WHERE id > 0) FROM test").unwrap();
+    parse_sql("SELECT group_concat(name, ',' ORDER BY name) FROM test").unwrap();
+    parse_sql("SELECT group_concat(DISTINCT name ORDER BY name DESC, id) FROM test").unwrap();
+    parse_sql("SELECT group_concat(name ORDER BY name NULLS FIRST) FROM test").unwrap();
+    parse_sql(
+        "SELECT group_concat(name ORDER BY id) FILTER (WHERE id > 0) OVER (PARTITION BY name) \
+         FROM test",
+    )
+    .unwrap();
+    parse_sql("SELECT count(*) FILTER (WHERE id > 0) OVER w FROM test WINDOW w AS ()").unwrap();
+    parse_sql("SELECT filter FROM filter").expect("FILTER is not a keyword here");
+
+    assert!(
+        parse_sql("SELECT count(*) FILTER (id > 0) FROM test").is_err(),
+        "error expected when WHERE is missing in FILTER clause"
+    );
+    assert!(
+        parse_sql("SELECT count(* ORDER BY id) FROM test").is_err(),
+        "error expected when ORDER BY is used with '*'"
+    );
+}
+
+#[test]
+fn test_window() {
+    parse_sql("SELECT row_number() OVER () FROM test").unwrap();
+    parse_sql("SELECT count(*) OVER (PARTITION BY name ORDER BY id) FROM test").unwrap();
+    parse_sql("SELECT count(*) OVER (ORDER BY id ASC NULLS LAST) FROM test").unwrap();
+    parse_sql("SELECT sum(id) OVER w FROM test WINDOW w AS (ORDER BY id)").unwrap();
+    parse_sql(
+        "SELECT sum(id) OVER (w ROWS CURRENT ROW) FROM test WINDOW w AS (PARTITION BY name), v \
+         AS (w ORDER BY id)",
+    )
+    .expect("base window supported");
+    parse_sql("SELECT sum(id) OVER (ROWS UNBOUNDED PRECEDING) FROM test").unwrap();
+    parse_sql("SELECT sum(id) OVER (RANGE 1 PRECEDING) FROM test").unwrap();
+    parse_sql(
+        "SELECT sum(id) OVER (ORDER BY id ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED \
+         FOLLOWING) FROM test",
+    )
+    .unwrap();
+    parse_sql(
+        "SELECT sum(id) OVER (ORDER BY id RANGE BETWEEN 1 PRECEDING AND 1 FOLLOWING EXCLUDE NO \
+         OTHERS) FROM test",
+    )
+    .unwrap();
+    parse_sql(
+        "SELECT sum(id) OVER (GROUPS BETWEEN CURRENT ROW AND 2 FOLLOWING EXCLUDE CURRENT ROW) \
+         FROM test",
+    )
+    .unwrap();
+    parse_sql("SELECT sum(id) OVER (GROUPS CURRENT ROW EXCLUDE GROUP) FROM test").unwrap();
+    parse_sql("SELECT sum(id) OVER (ROWS CURRENT ROW EXCLUDE TIES) FROM test").unwrap();
+    parse_sql("SELECT id FROM test GROUP BY id HAVING 1 WINDOW w AS ()").unwrap();
+
+    parse_sql("SELECT over FROM test").expect("OVER is not a keyword here");
+    parse_sql("SELECT count(*) over FROM test").expect("OVER is an alias here");
+    parse_sql("SELECT window FROM window").expect("WINDOW is not a keyword here");
+    parse_sql("SELECT partition, range, rows, groups FROM test").unwrap();
+    parse_sql("SELECT * FROM test WINDOW rows AS ()").expect("fallback keyword as window name");
+    parse_sql("SELECT sum(id) OVER range FROM test WINDOW range AS ()")
+        .expect("fallback keyword as window name");
+    parse_sql("SELECT * FROM test WINDOW left AS ()").expect("join keyword as window name");
+    parse_sql("SELECT sum(id) OVER full FROM test WINDOW full AS ()")
+        .expect("join keyword as window name");
+    parse_sql("SELECT sum(id) OVER over FROM test WINDOW over AS (), window AS (over)")
+        .expect("OVER and WINDOW as window names");
+
+    assert!(
+        parse_sql("SELECT sum(id) OVER (ROWS UNBOUNDED FOLLOWING) FROM test").is_err(),
+        "error expected when a frame starts with UNBOUNDED FOLLOWING"
+    );
+    assert!(
+        parse_sql(
+            "SELECT sum(id) OVER (ROWS BETWEEN 1 PRECEDING AND UNBOUNDED PRECEDING) FROM test"
+        )
+        .is_err(),
+        "error expected when a frame ends with UNBOUNDED PRECEDING"
+    );
+    assert!(
+        parse_sql("SELECT sum(id) OVER (ROWS CURRENT ROW EXCLUDE) FROM test").is_err(),
+        "error expected when EXCLUDE has no option"
+    );
+}
+
 #[test]
 fn test_create_index() {
     parse_sql("CREATE INDEX idx ON test (name)").unwrap();
@@ -301,6 +1055,10 @@ fn test_create_index() {
         parse_sql("CREATE INDEX idx ON test ()").is_err(),
         "error expected when no column name is specified"
     );
+    assert!(
+        parse_sql("CREATE INDEX idx ON test (name NULLS FIRST)").is_err(),
+        "error expected with NULLS FIRST"
+    );
 }
 
 #[test]
@@ -320,6 +1078,14 @@ fn test_drop_index() {
 fn test_vacuum() {
     parse_sql("VACUUM").unwrap();
     parse_sql("VACUUM main").unwrap();
+    parse_sql("VACUUM INTO 'backup.db'").unwrap();
+    parse_sql("VACUUM main INTO '/backups/x.db'").unwrap();
+    parse_sql("VACUUM main INTO ?").unwrap();
+
+    assert!(
+        parse_sql("VACUUM main INTO").is_err(),
+        "error expected when no file name is specified"
+    );
 }
 
 #[test]
@@ -330,10 +1096,65 @@ fn test_pragma() {
 
     parse_sql("PRAGMA name=1").unwrap();
 
+    assert_eq!(
+        PragmaValue::Numeric("-1".to_owned()),
+        pragma_value("PRAGMA journal_size_limit = -1")
+    );
+    assert_eq!(
+        PragmaValue::Numeric("+1.5".to_owned()),
+        pragma_value("PRAGMA name(+1.5)")
+    );
+    assert_eq!(
+        PragmaValue::Numeric("-0x10".to_owned()),
+        pragma_value("PRAGMA name = - 0x10")
+    );
+    assert_eq!(
+        PragmaValue::String("x''y".to_owned()),
+        pragma_value("PRAGMA key = 'x''y'")
+    );
+    assert_eq!(
+        PragmaValue::Name("wal".to_owned()),
+        pragma_value("PRAGMA journal_mode = wal")
+    );
+    assert_eq!(
+        PragmaValue::Name("full".to_owned()),
+        pragma_value("PRAGMA synchronous = FULL")
+    );
+    assert_eq!(
+        PragmaValue::Name("yes".to_owned()),
+        pragma_value("PRAGMA foreign_keys = yes")
+    );
+    assert_eq!(PragmaValue::On, pragma_value("PRAGMA foreign_keys = ON"));
+    assert_eq!(
+        PragmaValue::Delete,
+        pragma_value("PRAGMA journal_mode = DELETE")
+    );
+    assert_eq!(
+        PragmaValue::Default,
+        pragma_value("PRAGMA main.locking_mode(DEFAULT)")
+    );
+
     assert!(
         parse_sql("PRAGMA").is_err(),
         "error expected when no pragma name is specified"
     );
+    assert!(
+        parse_sql("PRAGMA name = NULL").is_err(),
+        "error expected with NULL pragma value"
+    );
+    assert!(
+        parse_sql("PRAGMA name = (1)").is_err(),
+        "error expected with expression as pragma value"
+    );
+}
+
+fn pragma_value(sql: &str) -> PragmaValue {
+    let Stmt::Pragma(_, Some(PragmaBody::Equals(value) | PragmaBody::Call(value))) =
+        single_stmt(sql)
+    else {
+        panic!("PRAGMA with value expected");
+    };
+    value
 }
 
 #[test]
@@ -341,8 +1162,11 @@ fn test_create_trigger() {
     parse_sql("CREATE TRIGGER trgr UPDATE ON test BEGIN SELECT 1; END").unwrap();
     parse_sql("CREATE TRIGGER main.trgr BEFORE UPDATE ON test BEGIN SELECT 1; END").unwrap();
 
-    // FIXME parse_sql("CREATE TRIGGER trgr BEFORE UPDATE ON test BEGIN SELECT
-    // RAISE(ABORT, '...') WHERE NEW.name <> OLD.name; END").unwrap();
+    parse_sql(
+        "CREATE TRIGGER trgr BEFORE UPDATE ON test BEGIN SELECT RAISE(ABORT, '...') WHERE NEW.name \
+         <> OLD.name; END",
+    )
+    .unwrap();
     parse_sql("CREATE TRIGGER IF NOT EXISTS trgr UPDATE ON test BEGIN SELECT 1; END").unwrap();
 
     assert!(
@@ -360,6 +1184,82 @@ fn test_create_trigger() {
     );
 }
 
+#[test]
+fn test_trigger_commands() {
+    parse_sql("CREATE TRIGGER trgr AFTER INSERT ON test BEGIN INSERT INTO log DEFAULT VALUES; END")
+        .unwrap();
+    parse_sql(
+        "CREATE TRIGGER trgr AFTER INSERT ON test BEGIN INSERT INTO log (id) SELECT NEW.id WHERE \
+         true ON CONFLICT (id) DO UPDATE SET n = n + 1; END",
+    )
+    .unwrap();
+    parse_sql(
+        "CREATE TRIGGER trgr AFTER INSERT ON test BEGIN INSERT OR IGNORE INTO log VALUES (1); END",
+    )
+    .unwrap();
+    parse_sql(
+        "CREATE TRIGGER trgr AFTER UPDATE ON test BEGIN UPDATE log SET n = s.n FROM stats AS s \
+         WHERE log.id = s.id; END",
+    )
+    .unwrap();
+    parse_sql(
+        "CREATE TRIGGER trgr AFTER DELETE ON test BEGIN DELETE FROM log WHERE id = OLD.id; END",
+    )
+    .unwrap();
+
+    assert_eq!(
+        ErrorCode::QualifiedNameInTrigger,
+        error_code("CREATE TRIGGER trgr AFTER DELETE ON test BEGIN DELETE FROM main.log; END")
+    );
+    assert_eq!(
+        ErrorCode::QualifiedNameInTrigger,
+        error_code(
+            "CREATE TRIGGER trgr AFTER DELETE ON test BEGIN INSERT INTO main.log VALUES (1); END"
+        )
+    );
+    assert_eq!(
+        ErrorCode::IndexedInTrigger,
+        error_code(
+            "CREATE TRIGGER trgr AFTER DELETE ON test BEGIN UPDATE log INDEXED BY idx SET n = 1; \
+             END"
+        )
+    );
+    assert_eq!(
+        ErrorCode::IndexedInTrigger,
+        error_code(
+            "CREATE TRIGGER trgr AFTER DELETE ON test BEGIN DELETE FROM log NOT INDEXED; END"
+        )
+    );
+    assert_eq!(
+        ErrorCode::ReturningInTrigger,
+        error_code(
+            "CREATE TRIGGER trgr AFTER DELETE ON test BEGIN DELETE FROM log RETURNING id; END"
+        )
+    );
+    assert_eq!(
+        ErrorCode::ReturningInTrigger,
+        error_code(
+            "CREATE TRIGGER trgr AFTER DELETE ON test BEGIN INSERT INTO log DEFAULT VALUES \
+             RETURNING *; END"
+        )
+    );
+    assert!(
+        parse_sql(
+            "CREATE TRIGGER trgr AFTER DELETE ON test BEGIN WITH x AS (SELECT 1) DELETE FROM log; \
+             END"
+        )
+        .is_err(),
+        "error expected with WITH clause on DELETE within a trigger"
+    );
+}
+
+fn error_code(sql: &str) -> ErrorCode {
+    match parse_sql(sql) {
+        Err(ParseError::User { error }) => error.code,
+        r => panic!("user error expected: {r:?}"),
+    }
+}
+
 #[test]
 fn test_drop_trigger() {
     parse_sql("DROP TRIGGER trgr").unwrap();
@@ -419,11 +1319,23 @@ fn test_alter_table() {
     parse_sql("ALTER TABLE main.test RENAME TO new").unwrap();
     parse_sql("ALTER TABLE test ADD new").unwrap();
     parse_sql("ALTER TABLE test ADD COLUMN new").unwrap();
+    parse_sql("ALTER TABLE test RENAME old TO new").unwrap();
+    parse_sql("ALTER TABLE test RENAME COLUMN old TO new").unwrap();
+    parse_sql("ALTER TABLE test DROP old").unwrap();
+    parse_sql("ALTER TABLE main.test DROP COLUMN old").unwrap();
 
     assert!(
         parse_sql("ALTER TABLE RENAME TO new").is_err(),
         "error expected when no table name is specified"
     );
+    assert!(
+        parse_sql("ALTER TABLE test RENAME COLUMN TO new").is_err(),
+        "error expected when no column name is specified"
+    );
+    assert!(
+        parse_sql("ALTER TABLE test DROP COLUMN").is_err(),
+        "error expected when no column name is specified"
+    );
 }
 
 #[test]
@@ -432,9 +1344,123 @@ fn test_create_virtual_table() {
     parse_sql("CREATE VIRTUAL TABLE main.test USING mod").unwrap();
     parse_sql("CREATE VIRTUAL TABLE test USING mod()").unwrap();
     parse_sql("CREATE VIRTUAL TABLE test USING mod('arg')").unwrap();
+    parse_sql("CREATE VIRTUAL TABLE test USING mod(f(x))").unwrap();
+
+    for (expected, sql) in [
+        (
+            vec!["title", "body", "tokenize = 'porter unicode61'"],
+            "CREATE VIRTUAL TABLE docs USING fts5(title, body, tokenize = 'porter unicode61')",
+        ),
+        (
+            vec!["id INTEGER", "minX REAL", "maxX REAL", "+label TEXT"],
+            "CREATE VIRTUAL TABLE r USING rtree(id INTEGER, minX REAL,maxX REAL, +label TEXT)",
+        ),
+        (
+            vec!["a", "f(x, (y))  /* c */ z", "','"],
+            "CREATE VIRTUAL TABLE test USING mod( a ,, f(x, (y))  /* c */ z, ',' )",
+        ),
+        (vec![], "CREATE VIRTUAL TABLE test USING mod()"),
+    ] {
+        let Stmt::CreateVirtualTable {
+            args: Some(args), ..
+        } = single_stmt(sql)
+        else {
+            panic!("CREATE VIRTUAL TABLE with arguments expected");
+        };
+        assert_eq!(expected, args.split());
+    }
 
     assert!(
         parse_sql("CREATE VIRTUAL TABLE test USING").is_err(),
         "error expected when no module name is specified"
     );
+    assert!(
+        parse_sql("CREATE VIRTUAL TABLE test USING mod(a, (b)").is_err(),
+        "error expected with unbalanced parentheses"
+    );
+}
+
+#[test]
+fn test_nested() {
+    parse_sql("SELECT f(g(h(i(j(k(1))))))").unwrap();
+    parse_sql("SELECT (SELECT (SELECT (SELECT 1)))").unwrap();
+}
+
+#[test]
+fn test_parser_options() {
+    let options = ParserOptions {
+        update_delete_limit: false,
+        triggers: false,
+        views: false,
+        virtual_tables: false,
+        ctes: false,
+        windows: false,
+        dqs: false,
+    };
+    parse_sql_with_options("DELETE FROM test WHERE id = 1", &options).unwrap();
+    parse_sql_with_options("UPDATE test SET n = 1 WHERE id = 1", &options).unwrap();
+    parse_sql_with_options("SELECT count(*) FROM test", &options).unwrap();
+
+    let Some(Cmd::Stmt(Stmt::Select(select))) = parse_sql_with_options("SELECT \"x\"", &options)
+        .unwrap()
+        .remove(0)
+    else {
+        panic!("SELECT expected");
+    };
+    let OneSelect::Select { columns, .. } = select.body.select else {
+        panic!("SELECT core expected");
+    };
+    assert_eq!(
+        vec![ResultColumn::Expr(Expr::Id("x".to_owned()), None)],
+        columns,
+        "double-quoted identifier without DQS"
+    );
+
+    for (sql, code) in [
+        (
+            "DELETE FROM test LIMIT 1",
+            ErrorCode::UnsupportedUpdateDeleteLimit,
+        ),
+        (
+            "DELETE FROM test ORDER BY id",
+            ErrorCode::UnsupportedUpdateDeleteLimit,
+        ),
+        (
+            "UPDATE test SET n = 1 ORDER BY id LIMIT 1",
+            ErrorCode::UnsupportedUpdateDeleteLimit,
+        ),
+        (
+            "CREATE TRIGGER trgr UPDATE ON test BEGIN SELECT 1; END",
+            ErrorCode::UnsupportedTrigger,
+        ),
+        ("DROP TRIGGER trgr", ErrorCode::UnsupportedTrigger),
+        ("CREATE VIEW v AS SELECT 1", ErrorCode::UnsupportedView),
+        ("DROP VIEW v", ErrorCode::UnsupportedView),
+        (
+            "CREATE VIRTUAL TABLE test USING mod",
+            ErrorCode::UnsupportedVirtualTable,
+        ),
+        (
+            "WITH x AS (SELECT 1) SELECT * FROM x",
+            ErrorCode::UnsupportedCte,
+        ),
+        (
+            "SELECT sum(n) OVER () FROM test",
+            ErrorCode::UnsupportedWindow,
+        ),
+        (
+            "SELECT count(*) FILTER (WHERE n > 0) FROM test",
+            ErrorCode::UnsupportedWindow,
+        ),
+        (
+            "SELECT sum(n) OVER w FROM test WINDOW w AS ()",
+            ErrorCode::UnsupportedWindow,
+        ),
+    ] {
+        match parse_sql_with_options(sql, &options) {
+            Err(ParseError::User { error }) => assert_eq!(code, error.code, "{sql}"),
+            r => panic!("{code:?} expected with {sql}: {r:?}"),
+        }
+        parse_sql_with_options(sql, &ParserOptions::default()).unwrap();
+    }
 }
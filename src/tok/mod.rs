@@ -28,9 +28,24 @@ pub enum ErrorCode {
     ExpectedEqualsSign,
     MalformedBlobLiteral,
     MalformedHexInteger,
+    UnknownGeneratedColumnType,
+    UnknownTableOption,
+    UnknownJoinType,
+    UnsupportedNullsOrder,
+    // SQLite restrictions on statements within triggers
+    QualifiedNameInTrigger,
+    IndexedInTrigger,
+    ReturningInTrigger,
+    // syntax disabled by `ParserOptions`
+    UnsupportedUpdateDeleteLimit,
+    UnsupportedTrigger,
+    UnsupportedView,
+    UnsupportedVirtualTable,
+    UnsupportedCte,
+    UnsupportedWindow,
 }
 
-fn error<T>(c: ErrorCode, l: usize, t: &str) -> Result<T, Error> {
+pub(crate) fn error<T>(c: ErrorCode, l: usize, t: &str) -> Result<T, Error> {
     let line = t[..l].chars().filter(|c| *c == '\n').count() + 1;
     Err(Error {
         location: l,
@@ -39,11 +54,22 @@ fn error<T>(c: ErrorCode, l: usize, t: &str) -> Result<T, Error> {
     })
 }
 
+#[derive(Clone)]
 pub struct Tokenizer<'input> {
     text: &'input str,
     chars: CharIndices<'input>,
     lookahead: Option<(usize, char)>,
     shift: usize,
+    // true when the last token was a ')'
+    after_right_paren: bool,
+    // true when the last token was USING
+    after_using: bool,
+    // true when the last token was the name following USING
+    after_module_name: bool,
+    // true when the last token was ATTACH or DETACH
+    after_attach: bool,
+    // true in a lookahead, where keywords are not analyzed in their context (getToken)
+    raw: bool,
 }
 
 pub type Spanned<T> = (usize, T, usize);
@@ -56,6 +82,7 @@ pub enum Tok<'input> {
     Add,
     After,
     All,
+    Always,
     Alter,
     Analyze,
     And,
@@ -79,6 +106,7 @@ pub enum Tok<'input> {
     Constraint,
     Create,
     Cross,
+    Current,
     CurrentDate,
     CurrentTime,
     CurrentTimestamp,
@@ -90,23 +118,30 @@ pub enum Tok<'input> {
     Desc,
     Detach,
     Distinct,
+    Do,
     Drop,
     Each,
     Else,
     End,
     Escape,
     Except,
+    Exclude,
     Exclusive,
     Exists,
     Explain,
     Fail,
+    Filter,
+    First,
+    Following,
     For,
     Foreign,
     From,
+    Generated,
     Full,
     // Function,
     Glob,
     Group,
+    Groups,
     Having,
     If,
     Ignore,
@@ -125,26 +160,35 @@ pub enum Tok<'input> {
     IsNull,
     Join,
     Key,
+    Last,
     Left,
     Like,
     Limit,
     Match,
+    Materialized,
     Natural,
     No,
     Not,
     NotNull,
+    Nothing,
     Null,
+    Nulls,
     Of,
     Offset,
     On,
     Or,
     Order,
+    Others,
     Outer,
+    Over,
+    Partition,
     Plan,
     Pragma,
+    Preceding,
     Primary,
     Query,
     Raise,
+    Range,
     Recursive,
     References,
     Regexp,
@@ -153,18 +197,22 @@ pub enum Tok<'input> {
     Rename,
     Replace,
     Restrict,
+    Returning,
     Right,
     Rollback,
     Row,
+    Rows,
     Savepoint,
     Select,
     Set,
     Table,
     Temp,
     Then,
+    Ties,
     To,
     Transaction,
     Trigger,
+    Unbounded,
     Union,
     Unique,
     Update,
@@ -175,6 +223,7 @@ pub enum Tok<'input> {
     Virtual,
     When,
     Where,
+    Window,
     With,
     Without,
 
@@ -183,12 +232,17 @@ pub enum Tok<'input> {
     Id(&'input str),
     Variable(&'input str),
 
+    // Virtual table module arguments (source text between the parentheses):
+    ModuleArguments(&'input str),
+
     // Values:
     Blob(&'input str),
     Integer(&'input str),
     Float(&'input str),
 
     // Symbols:
+    ArrowRight,
+    ArrowRightShift,
     BitAnd,
     BitNot,
     BitOr,
@@ -214,12 +268,13 @@ pub enum Tok<'input> {
 }
 
 #[rustfmt::skip]
-const KEYWORDS: &[(&str, Tok<'static>)] = &[
+pub(crate) const KEYWORDS: &[(&str, Tok<'static>)] = &[
     ("ABORT", Abort),
     ("ACTION", Action),
     ("ADD", Add),
     ("AFTER", After),
     ("ALL", All),
+    ("ALWAYS", Always),
     ("ALTER", Alter),
     ("ANALYZE", Analyze),
     ("AND", And),
@@ -242,6 +297,7 @@ const KEYWORDS: &[(&str, Tok<'static>)] = &[
     ("CONSTRAINT", Constraint),
     ("CREATE", Create),
     ("CROSS", Cross),
+    ("CURRENT", Current),
     ("CURRENT_DATE", CurrentDate),
     ("CURRENT_TIME", CurrentTime),
     ("CURRENT_TIMESTAMP", CurrentTimestamp),
@@ -253,22 +309,29 @@ const KEYWORDS: &[(&str, Tok<'static>)] = &[
     ("DESC", Desc),
     ("DETACH", Detach),
     ("DISTINCT", Distinct),
+    ("DO", Do),
     ("DROP", Drop),
     ("EACH", Each),
     ("ELSE", Else),
     ("END", End),
     ("ESCAPE", Escape),
     ("EXCEPT", Except),
+    ("EXCLUDE", Exclude),
     ("EXCLUSIVE", Exclusive),
     ("EXISTS", Exists),
     ("EXPLAIN", Explain),
     ("FAIL", Fail),
+    ("FILTER", Filter),
+    ("FIRST", First),
+    ("FOLLOWING", Following),
     ("FOR", For),
     ("FOREIGN", Foreign),
     ("FROM", From),
     ("FULL", Full),
+    ("GENERATED", Generated),
     ("GLOB", Glob),
     ("GROUP", Group),
+    ("GROUPS", Groups),
     ("HAVING", Having),
     ("IF", If),
     ("IGNORE", Ignore),
@@ -286,26 +349,35 @@ const KEYWORDS: &[(&str, Tok<'static>)] = &[
     ("ISNULL", IsNull),
     ("JOIN", Join),
     ("KEY", Key),
+    ("LAST", Last),
     ("LEFT", Left),
     ("LIKE", Like),
     ("LIMIT", Limit),
     ("MATCH", Match),
+    ("MATERIALIZED", Materialized),
     ("NATURAL", Natural),
     ("NO", No),
     ("NOT", Not),
+    ("NOTHING", Nothing),
     ("NOTNULL", NotNull),
     ("NULL", Null),
+    ("NULLS", Nulls),
     ("OF", Of),
     ("OFFSET", Offset),
     ("ON", On),
     ("OR", Or),
     ("ORDER", Order),
+    ("OTHERS", Others),
     ("OUTER", Outer),
+    ("OVER", Over),
+    ("PARTITION", Partition),
     ("PLAN", Plan),
     ("PRAGMA", Pragma),
+    ("PRECEDING", Preceding),
     ("PRIMARY", Primary),
     ("QUERY", Query),
     ("RAISE", Raise),
+    ("RANGE", Range),
     ("RECURSIVE", Recursive),
     ("REFERENCES", References),
     ("REGEXP", Regexp),
@@ -314,9 +386,11 @@ const KEYWORDS: &[(&str, Tok<'static>)] = &[
     ("RENAME", Rename),
     ("REPLACE", Replace),
     ("RESTRICT", Restrict),
+    ("RETURNING", Returning),
     ("RIGHT", Right),
     ("ROLLBACK", Rollback),
     ("ROW", Row),
+    ("ROWS", Rows),
     ("SAVEPOINT", Savepoint),
     ("SELECT", Select),
     ("SET", Set),
@@ -324,9 +398,11 @@ const KEYWORDS: &[(&str, Tok<'static>)] = &[
     ("TEMP", Temp),
     ("TEMPORARY", Temp),
     ("THEN", Then),
+    ("TIES", Ties),
     ("TO", To),
     ("TRANSACTION", Transaction),
     ("TRIGGER", Trigger),
+    ("UNBOUNDED", Unbounded),
     ("UNION", Union),
     ("UNIQUE", Unique),
     ("UPDATE", Update),
@@ -337,10 +413,97 @@ const KEYWORDS: &[(&str, Tok<'static>)] = &[
     ("VIRTUAL", Virtual),
     ("WHEN", When),
     ("WHERE", Where),
+    ("WINDOW", Window),
     ("WITH", With),
     ("WITHOUT", Without)
     ];
 
+impl Tok<'_> {
+    // %fallback ID (including LIKE_KW and CTIME_KW), see the grammar `Id` rule
+    pub fn is_fallback_id(&self) -> bool {
+        matches!(
+            self,
+            Abort
+                | Action
+                | After
+                | Always
+                | Analyze
+                | Asc
+                | Attach
+                | Before
+                | Begin
+                | By
+                | Cascade
+                | Cast
+                | ColumnKw
+                | Conflict
+                | Current
+                | CurrentDate
+                | CurrentTime
+                | CurrentTimestamp
+                | Database
+                | Deferred
+                | Desc
+                | Detach
+                | Do
+                | Each
+                | End
+                | Exclude
+                | Exclusive
+                | Explain
+                | Fail
+                | First
+                | Following
+                | For
+                | Generated
+                | Glob
+                | Groups
+                | If
+                | Ignore
+                | Immediate
+                | Initially
+                | Instead
+                | Key
+                | Last
+                | Like
+                | Match
+                | Materialized
+                | No
+                | Nulls
+                | Of
+                | Offset
+                | Others
+                | Partition
+                | Plan
+                | Pragma
+                | Preceding
+                | Query
+                | Raise
+                | Range
+                | Recursive
+                | Regexp
+                | Reindex
+                | Release
+                | Rename
+                | Replace
+                | Restrict
+                | Rollback
+                | Row
+                | Rows
+                | Savepoint
+                | Temp
+                | Ties
+                | Trigger
+                | Unbounded
+                | Vacuum
+                | View
+                | Virtual
+                | With
+                | Without
+        )
+    }
+}
+
 impl<'input> Tokenizer<'input> {
     pub fn new(text: &'input str, shift: usize) -> Tokenizer<'input> {
         let mut t = Tokenizer {
@@ -348,6 +511,11 @@ impl<'input> Tokenizer<'input> {
             chars: text.char_indices(),
             lookahead: None,
             shift,
+            after_right_paren: false,
+            after_using: false,
+            after_module_name: false,
+            after_attach: false,
+            raw: false,
         };
         t.bump();
         t
@@ -366,9 +534,21 @@ impl<'input> Tokenizer<'input> {
                         self.take_until(|c| c == '\n');
                         continue;
                     }
+                    Some((idx1, '>')) => match self.bump() {
+                        Some((idx2, '>')) => {
+                            self.bump();
+                            Some(Ok((idx0, ArrowRightShift, idx2 + 1)))
+                        }
+                        _ => Some(Ok((idx0, ArrowRight, idx1 + 1))),
+                    },
                     _ => Some(Ok((idx0, Minus, idx0 + 1))),
                 },
                 Some((idx0, '(')) => {
+                    if self.after_module_name
+                        && let Some(t) = self.module_arguments(idx0)
+                    {
+                        return Some(Ok(t));
+                    }
                     self.bump();
                     Some(Ok((idx0, LeftParen, idx0 + 1)))
                 }
@@ -478,13 +658,7 @@ impl<'input> Tokenizer<'input> {
                 }
                 Some((idx0, c)) if c == '$' || c == '@' || c == '#' || c == ':' => {
                     self.bump();
-                    // '$' is included as part of the name
-                    let (start, name, end) = self.word(idx0);
-                    if name.len() == 1 {
-                        Some(error(BadVariableName, idx0, self.text))
-                    } else {
-                        Some(Ok((start, Variable(name), end)))
-                    }
+                    Some(self.variable(idx0))
                 }
                 Some((idx0, c)) if is_identifier_start(c) => {
                     if c == 'x' || c == 'X' {
@@ -567,7 +741,7 @@ impl<'input> Tokenizer<'input> {
 
     // Real
     fn fractional_part(&mut self, idx0: usize) -> Result<Spanned<Tok<'input>>, Error> {
-        match self.take_while(|c| c.is_ascii_digit()) {
+        match self.take_digits(|c| c.is_ascii_digit()).1 {
             Some((end, c)) => {
                 if c == 'e' || c == 'E' {
                     self.exponential_part(idx0)
@@ -591,7 +765,7 @@ impl<'input> Tokenizer<'input> {
             _ => {}
         };
 
-        match self.take_while_1(|c| c.is_ascii_digit()) {
+        match self.take_digits(|c| c.is_ascii_digit()) {
             (false, _) => error(BadNumber, idx0, self.text),
             (true, Some((end, c))) => {
                 if is_identifier_start(c) {
@@ -616,7 +790,7 @@ impl<'input> Tokenizer<'input> {
                 _ => {}
             }
         }
-        match self.take_while(|c| c.is_ascii_digit()) {
+        match self.take_digits(|c| c.is_ascii_digit()).1 {
             Some((end, c)) => {
                 if c == '.' {
                     self.bump();
@@ -636,7 +810,7 @@ impl<'input> Tokenizer<'input> {
 
     fn hex_integer(&mut self, idx0: usize) -> Result<Spanned<Tok<'input>>, Error> {
         // Must not be empty (Ox is invalid)
-        match self.take_while_1(|c| c.is_ascii_hexdigit()) {
+        match self.take_digits(|c| c.is_ascii_hexdigit()) {
             (false, _) => {
                 self.word(idx0);
                 error(MalformedHexInteger, idx0, self.text)
@@ -662,9 +836,108 @@ impl<'input> Tokenizer<'input> {
             .map(|(_, t)| t.clone())
             .next()
             .unwrap_or(Id(word));
+        if self.raw {
+            return Ok((start, tok, end));
+        }
+        let tok = match tok {
+            Filter if !self.is_filter_keyword() => Id(word),
+            Over if !self.is_over_keyword() => Id(word),
+            Window if !self.is_window_keyword() => Id(word),
+            tok => tok,
+        };
         Ok((start, tok, end))
     }
 
+    fn raw_lookahead(&self) -> Tokenizer<'input> {
+        Tokenizer {
+            raw: true,
+            ..self.clone()
+        }
+    }
+
+    // analyzeFilterKeyword: after a ')' and before a '('
+    fn is_filter_keyword(&self) -> bool {
+        if !self.after_right_paren {
+            return false;
+        }
+        let mut t = self.raw_lookahead();
+        matches!(t.next_unshifted(), Some(Ok((_, LeftParen, _))))
+    }
+
+    // analyzeOverKeyword: after a ')' and before a '(' or a name
+    fn is_over_keyword(&self) -> bool {
+        if !self.after_right_paren {
+            return false;
+        }
+        let mut t = self.raw_lookahead();
+        match t.next_unshifted() {
+            Some(Ok((_, LeftParen, _))) => true,
+            Some(Ok((_, tok, _))) => is_name(&tok),
+            _ => false,
+        }
+    }
+
+    // analyzeWindowKeyword: before a name and AS
+    fn is_window_keyword(&self) -> bool {
+        let mut t = self.raw_lookahead();
+        matches!(t.next_unshifted(), Some(Ok((_, tok, _))) if is_name(&tok))
+            && matches!(t.next_unshifted(), Some(Ok((_, As, _))))
+    }
+
+    // vtabargs: the arguments of `USING module(...)` are one token (with balanced parentheses)
+    fn module_arguments(&mut self, idx0: usize) -> Option<Spanned<Tok<'input>>> {
+        let mut t = self.clone();
+        t.bump(); // consume the '('
+        let mut depth = 1;
+        while let Some(Ok((_, tok, r))) = t.next_unshifted() {
+            match tok {
+                LeftParen => depth += 1,
+                RightParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        *self = t;
+                        return Some((idx0, ModuleArguments(&self.text[idx0 + 1..r - 1]), r));
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    // TCL-style names: `$ns::var` or `$arr(index)`
+    fn variable(&mut self, idx0: usize) -> Result<Spanned<Tok<'input>>, Error> {
+        let mut n = 0;
+        loop {
+            match self.lookahead {
+                Some((_, c)) if is_identifier_continue(c) => {
+                    n += 1;
+                    self.bump();
+                }
+                Some((_, '(')) if n > 0 => {
+                    match self.take_until(|c| c.is_whitespace() || c == ')') {
+                        Some(idx1) if self.lookahead == Some((idx1, ')')) => {
+                            self.bump(); // consume the ')'
+                            break;
+                        }
+                        _ => return error(BadVariableName, idx0, self.text),
+                    }
+                }
+                Some((idx1, ':')) if self.text[idx1 + 1..].starts_with(':') => {
+                    self.bump();
+                    self.bump();
+                }
+                _ => break,
+            }
+        }
+        if n == 0 {
+            return error(BadVariableName, idx0, self.text);
+        }
+        // '$' is included as part of the name
+        let end = self.lookahead.map_or(self.text.len(), |(idx1, _)| idx1);
+        Ok((idx0, Variable(&self.text[idx0..end]), end))
+    }
+
     fn word(&mut self, idx0: usize) -> Spanned<&'input str> {
         match self.take_while(is_identifier_continue) {
             Some((end, _)) => (idx0, &self.text[idx0..end], end),
@@ -724,6 +997,28 @@ impl<'input> Tokenizer<'input> {
         }
     }
 
+    // Like `take_while_1(is_digit)` but with SQLite digit separators: '_' is only allowed between two
+    // digits.
+    fn take_digits<F>(&mut self, is_digit: F) -> (bool, Option<(usize, char)>)
+    where
+        F: Fn(char) -> bool + Copy,
+    {
+        let mut succeed = false;
+        loop {
+            match self.lookahead {
+                Some((_, c)) if is_digit(c) => {}
+                Some((idx, '_'))
+                    if self.text[..idx].ends_with(is_digit)
+                        && self.text[idx + 1..].starts_with(is_digit) => {}
+                _ => {
+                    return (succeed, self.lookahead);
+                }
+            }
+            self.bump();
+            succeed = true;
+        }
+    }
+
     fn take_while<F>(&mut self, mut keep_going: F) -> Option<(usize, char)>
     where
         F: FnMut(char) -> bool,
@@ -771,7 +1066,19 @@ impl<'input> Iterator for Tokenizer<'input> {
     fn next(&mut self) -> Option<Result<Spanned<Tok<'input>>, Error>> {
         match self.next_unshifted() {
             None => None,
-            Some(Ok((l, t, r))) => Some(Ok((l + self.shift, t, r + self.shift))),
+            Some(Ok((l, t, r))) => {
+                // DATABASE is only a keyword after ATTACH or DETACH
+                let t = if t == Database && !self.after_attach {
+                    Id(&self.text[l..r])
+                } else {
+                    t
+                };
+                self.after_attach = t == Attach || t == Detach;
+                self.after_right_paren = t == RightParen;
+                self.after_module_name = self.after_using && t != LeftParen;
+                self.after_using = t == Using;
+                Some(Ok((l + self.shift, t, r + self.shift)))
+            }
             Some(Err(Error {
                 location,
                 code,
@@ -785,6 +1092,25 @@ impl<'input> Iterator for Tokenizer<'input> {
     }
 }
 
+// TK_ID (including fallback keywords), TK_STRING, TK_JOIN_KW, TK_OVER or TK_WINDOW:
+// a token which can be a window name
+fn is_name(tok: &Tok) -> bool {
+    matches!(
+        tok,
+        Id(_)
+            | StringLiteral(_)
+            | Cross
+            | Full
+            | Inner
+            | Left
+            | Natural
+            | Outer
+            | Right
+            | Over
+            | Window
+    ) || tok.is_fallback_id()
+}
+
 fn is_identifier_start(c: char) -> bool {
     c.is_ascii_uppercase() || c == '_' || c.is_ascii_lowercase() || c > '\x7F'
 }
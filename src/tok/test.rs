@@ -104,6 +104,28 @@ fn test_minus() {
         Tok::Id("b"),
     ];
     assert_tokens(expected_tokens, "SELECT a - -b");
+    let expected_tokens = vec![
+        Tok::Select,
+        Tok::Id("a"),
+        Tok::ArrowRight,
+        Tok::StringLiteral("$.b"),
+    ];
+    assert_tokens(expected_tokens, "SELECT a->'$.b'");
+    let expected_tokens = vec![
+        Tok::Select,
+        Tok::Id("a"),
+        Tok::ArrowRightShift,
+        Tok::StringLiteral("$.b"),
+    ];
+    assert_tokens(expected_tokens, "SELECT a->>'$.b'");
+    let expected_tokens = vec![
+        Tok::Select,
+        Tok::Id("a"),
+        Tok::Minus,
+        Tok::GreaterThan,
+        Tok::Id("b"),
+    ];
+    assert_tokens(expected_tokens, "SELECT a- >b");
 }
 
 #[test]
@@ -326,6 +348,39 @@ fn test_number() {
     assert_error(expected_tokens, "SELECT 1.0e5_");
 }
 
+#[test]
+fn test_digit_separator() {
+    let expected_tokens = vec![Tok::Select, Tok::Integer("1_000_000")];
+    assert_tokens(expected_tokens, "SELECT 1_000_000");
+    let expected_tokens = vec![Tok::Select, Tok::Integer("0_1")];
+    assert_tokens(expected_tokens, "SELECT 0_1");
+    let expected_tokens = vec![Tok::Select, Tok::Float("1_0.2_5e1_0")];
+    assert_tokens(expected_tokens, "SELECT 1_0.2_5e1_0");
+    let expected_tokens = vec![Tok::Select, Tok::Float(".5_5")];
+    assert_tokens(expected_tokens, "SELECT .5_5");
+
+    let expected_tokens = vec![Ok(Tok::Select), super::error(ErrorCode::BadNumber, 0, "")];
+    assert_error(expected_tokens, "SELECT 1__0");
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::BadNumber, 0, ""),
+        Ok(Tok::Float(".5")),
+    ];
+    assert_error(expected_tokens, "SELECT 1_.5");
+    let expected_tokens = vec![Ok(Tok::Select), super::error(ErrorCode::BadNumber, 0, "")];
+    assert_error(expected_tokens, "SELECT 1._5");
+    let expected_tokens = vec![Ok(Tok::Select), super::error(ErrorCode::BadNumber, 0, "")];
+    assert_error(expected_tokens, "SELECT 1_e5");
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::BadNumber, 0, ""),
+        Ok(Tok::Id("_5")),
+    ];
+    assert_error(expected_tokens, "SELECT 1e_5");
+    let expected_tokens = vec![Tok::Select, Tok::Id("_1")];
+    assert_tokens(expected_tokens, "SELECT _1");
+}
+
 #[test]
 fn test_hex_integer() {
     let expected_tokens = vec![Tok::Select, Tok::Integer("0x5")];
@@ -346,6 +401,19 @@ fn test_hex_integer() {
         super::error(ErrorCode::MalformedHexInteger, 0, ""),
     ];
     assert_error(expected_tokens, "SELECT 0Xg");
+
+    let expected_tokens = vec![Tok::Select, Tok::Integer("0xFF_FF")];
+    assert_tokens(expected_tokens, "SELECT 0xFF_FF");
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::MalformedHexInteger, 0, ""),
+    ];
+    assert_error(expected_tokens, "SELECT 0x_FF_FF");
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::MalformedHexInteger, 0, ""),
+    ];
+    assert_error(expected_tokens, "SELECT 0xFF_");
 }
 
 #[test]
@@ -432,6 +500,51 @@ fn test_colon() {
     assert_error(expected_tokens, "SELECT :,");
 }
 
+#[test]
+fn test_tcl_variable() {
+    let expected_tokens = vec![Tok::Select, Tok::Variable("$ns::var")];
+    assert_tokens(expected_tokens, "SELECT $ns::var");
+    let expected_tokens = vec![Tok::Select, Tok::Variable("$::var")];
+    assert_tokens(expected_tokens, "SELECT $::var");
+    let expected_tokens = vec![Tok::Select, Tok::Variable(":a::b::c")];
+    assert_tokens(expected_tokens, "SELECT :a::b::c");
+    let expected_tokens = vec![Tok::Select, Tok::Variable("$ns"), Tok::Variable(":var")];
+    assert_tokens(expected_tokens, "SELECT $ns:var");
+
+    let expected_tokens = vec![Tok::Select, Tok::Variable("$arr(index)"), Tok::Comma];
+    assert_tokens(expected_tokens, "SELECT $arr(index),");
+    let expected_tokens = vec![Tok::Select, Tok::Variable("$ns::arr(a,b(c)")];
+    assert_tokens(expected_tokens, "SELECT $ns::arr(a,b(c)");
+    let expected_tokens = vec![Tok::Select, Tok::Variable("$arr()")];
+    assert_tokens(expected_tokens, "SELECT $arr()");
+
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::BadVariableName, 0, ""),
+    ];
+    assert_error(expected_tokens, "SELECT $::");
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::BadVariableName, 0, ""),
+        Ok(Tok::LeftParen),
+        Ok(Tok::Id("a")),
+        Ok(Tok::RightParen),
+    ];
+    assert_error(expected_tokens, "SELECT $(a)");
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::BadVariableName, 0, ""),
+    ];
+    assert_error(expected_tokens, "SELECT $arr(index");
+    let expected_tokens = vec![
+        Ok(Tok::Select),
+        super::error(ErrorCode::BadVariableName, 0, ""),
+        Ok(Tok::Id("b")),
+        Ok(Tok::RightParen),
+    ];
+    assert_error(expected_tokens, "SELECT $arr(a b)");
+}
+
 #[test]
 fn test_blob_literal() {
     let expected_tokens = vec![Tok::Select, Tok::Id("x0")];
@@ -472,3 +585,107 @@ fn test_illegal_char() {
     let expected_tokens = vec![super::error(ErrorCode::UnrecognizedToken, 0, "")];
     assert_error(expected_tokens, "}");
 }
+
+#[test]
+fn test_filter() {
+    let expected_tokens = vec![
+        Tok::Id("f"),
+        Tok::LeftParen,
+        Tok::RightParen,
+        Tok::Filter,
+        Tok::LeftParen,
+    ];
+    assert_tokens(expected_tokens, "f() FILTER (");
+    let expected_tokens = vec![
+        Tok::Id("f"),
+        Tok::LeftParen,
+        Tok::RightParen,
+        Tok::Id("filter"),
+    ];
+    assert_tokens(expected_tokens, "f() filter");
+    let expected_tokens = vec![Tok::Id("filter"), Tok::LeftParen];
+    assert_tokens(expected_tokens, "filter(");
+
+    let mut expected_tokens = vec![Tok::Id("f"), Tok::LeftParen, Tok::RightParen];
+    expected_tokens.extend(std::iter::repeat_n(Tok::Id("filter"), 10_000));
+    assert_tokens(
+        expected_tokens,
+        &format!("f() {}", "filter ".repeat(10_000)),
+    );
+}
+
+#[test]
+fn test_over() {
+    let expected_tokens = vec![
+        Tok::Id("f"),
+        Tok::LeftParen,
+        Tok::RightParen,
+        Tok::Over,
+        Tok::Id("w"),
+    ];
+    assert_tokens(expected_tokens, "f() OVER w");
+    let expected_tokens = vec![
+        Tok::Id("f"),
+        Tok::LeftParen,
+        Tok::RightParen,
+        Tok::Over,
+        Tok::LeftParen,
+    ];
+    assert_tokens(expected_tokens, "f() OVER (");
+    let expected_tokens = vec![
+        Tok::Id("f"),
+        Tok::LeftParen,
+        Tok::RightParen,
+        Tok::Id("over"),
+    ];
+    assert_tokens(expected_tokens, "f() over");
+    let expected_tokens = vec![Tok::Select, Tok::Id("over"), Tok::Id("w")];
+    assert_tokens(expected_tokens, "SELECT over w");
+    let expected_tokens = vec![Tok::RightParen, Tok::Over, Tok::Range];
+    assert_tokens(expected_tokens, ") OVER range");
+    let expected_tokens = vec![Tok::RightParen, Tok::Over, Tok::Full];
+    assert_tokens(expected_tokens, ") OVER full");
+
+    let mut expected_tokens = vec![Tok::Id("f"), Tok::LeftParen, Tok::RightParen, Tok::Over];
+    expected_tokens.extend(std::iter::repeat_n(Tok::Id("over"), 9_999));
+    assert_tokens(expected_tokens, &format!("f() {}", "over ".repeat(10_000)));
+}
+
+#[test]
+fn test_window() {
+    let expected_tokens = vec![Tok::Window, Tok::Id("w"), Tok::As];
+    assert_tokens(expected_tokens, "WINDOW w AS");
+    let expected_tokens = vec![Tok::Window, Tok::Rows, Tok::As];
+    assert_tokens(expected_tokens, "WINDOW rows AS");
+    let expected_tokens = vec![Tok::Window, Tok::Left, Tok::As];
+    assert_tokens(expected_tokens, "WINDOW left AS");
+    let expected_tokens = vec![Tok::From, Tok::Id("window")];
+    assert_tokens(expected_tokens, "FROM window");
+    let expected_tokens = vec![Tok::From, Tok::Id("window"), Tok::Id("w")];
+    assert_tokens(expected_tokens, "FROM window w");
+
+    let mut expected_tokens = vec![Tok::Select];
+    expected_tokens.extend(std::iter::repeat_n(Tok::Id("window"), 50));
+    assert_tokens(expected_tokens, &format!("SELECT {}", "window ".repeat(50)));
+}
+
+#[test]
+fn test_module_arguments() {
+    let expected_tokens = vec![
+        Tok::Create,
+        Tok::Virtual,
+        Tok::Table,
+        Tok::Id("t"),
+        Tok::Using,
+        Tok::Id("m"),
+        Tok::ModuleArguments("a, f(x), ')'"),
+    ];
+    assert_tokens(
+        expected_tokens,
+        "CREATE VIRTUAL TABLE t USING m(a, f(x), ')')",
+    );
+    let expected_tokens = vec![Tok::Using, Tok::LeftParen, Tok::Id("a"), Tok::RightParen];
+    assert_tokens(expected_tokens, "USING (a)");
+    let expected_tokens = vec![Tok::Using, Tok::Id("m"), Tok::LeftParen, Tok::Id("a")];
+    assert_tokens(expected_tokens, "USING m(a");
+}